@@ -0,0 +1,325 @@
+//! A blocking version of [`Mpris`](crate::Mpris) and [`Player`](crate::Player), behind the `blocking` feature, for scripts and status bars that don't want an async runtime.
+//! <br>The async calls are run on an internal tokio runtime, which also keeps the connections running in the background.
+//! The streams are turned into [`Iter`]ators, which block until the next item.
+//! <br><br>The calls panic if made from inside an async runtime, use the async API there.
+//! ```no_run
+//! # fn main() -> Result<(), mpris_client_async::Error> {
+//! use mpris_client_async::{blocking::Mpris, properties::Metadata};
+//!
+//! let mpris = Mpris::new()?;
+//! for player in mpris.get_players()? {
+//!     println!("{}: {}", player.display_name(), player.get(Metadata)?.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{pin::Pin, sync::{Arc, OnceLock}, time::Duration};
+
+use futures::{Stream, StreamExt as _};
+use tokio::sync::{mpsc, oneshot};
+use zbus::{Connection, names::OwnedBusName, zvariant::{OwnedValue, Type, Value}};
+
+use crate::{BusKind, Error, PlayerEvent, PlayerProperties, properties::{ControlWritableProperty, Property, WritableProperty}, signals::Signal, streams::{NowPlaying, StreamYield}};
+
+/// The runtime every blocking call runs on. It's multi-threaded, so the connections keep being served between the calls.
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("mpris-blocking")
+            .enable_all()
+            .build()
+            .expect("Failed to start the runtime of the blocking API")
+    })
+}
+
+/// Runs a future of the async API to completion on the internal runtime. Useful for the calls this module doesn't mirror.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    runtime().block_on(future)
+}
+
+/// A stream of the async API as a blocking iterator. Each [`next`](Iterator::next) blocks until the stream yields.
+pub struct Iter<T> {
+    stream: Pin<Box<dyn Stream<Item = T> + Send>>
+}
+impl<T> Iter<T> {
+    fn new(stream: impl Stream<Item = T> + Send + 'static) -> Self {
+        Self { stream: Box::pin(stream) }
+    }
+}
+impl<T> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.stream.next())
+    }
+}
+
+/// The blocking version of [`crate::Mpris`]
+#[derive(Debug, Clone)]
+pub struct Mpris {
+    inner: crate::Mpris<'static>
+}
+impl Mpris {
+    /// Connects to the session bus, see [`crate::Mpris::new`]
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self { inner: block_on(crate::Mpris::new())? })
+    }
+
+    /// Connects to the system bus, see [`crate::Mpris::new_system`]
+    pub fn new_system() -> Result<Self, Error> {
+        Ok(Self { inner: block_on(crate::Mpris::new_system())? })
+    }
+
+    /// Wraps an already existing connection, see [`crate::Mpris::new_from_connection`]
+    pub fn new_from_connection(connection: Connection) -> Result<Self, Error> {
+        Ok(Self { inner: block_on(crate::Mpris::new_from_connection(connection))? })
+    }
+
+    /// The async version, to use with [`block_on`] for the calls this doesn't mirror
+    pub fn inner(&self) -> &crate::Mpris<'static> {
+        &self.inner
+    }
+
+    /// See [`crate::Mpris::bus_kind`]
+    pub fn bus_kind(&self) -> &BusKind {
+        self.inner.bus_kind()
+    }
+
+    /// See [`crate::Mpris::get_players`]
+    pub fn get_players(&self) -> Result<Vec<Player>, Error> {
+        Ok(block_on(self.inner.get_players())?.into_iter().map(Player::from).collect())
+    }
+
+    /// See [`crate::Mpris::primary_player`]
+    pub fn primary_player(&self) -> Result<Player, Error> {
+        block_on(self.inner.primary_player()).map(Player::from)
+    }
+
+    /// See [`crate::Mpris::playing_players`]
+    pub fn playing_players(&self) -> Result<Vec<Player>, Error> {
+        Ok(block_on(self.inner.playing_players())?.into_iter().map(Player::from).collect())
+    }
+
+    /// See [`crate::Mpris::player_stream`]
+    pub fn player_stream(&self) -> Result<Iter<PlayerEvent>, Error> {
+        let inner = self.inner.clone();
+        let (ready, started) = oneshot::channel();
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        // The stream borrows the Mpris, so it's consumed on a task owning it. The task ends with the next event after the iterator is dropped.
+        runtime().spawn(async move {
+            let events = match inner.player_stream().await {
+                Ok(events) => events,
+                Err(e) => {
+                    _ = ready.send(Err(e));
+                    return;
+                }
+            };
+            futures::pin_mut!(events);
+            _ = ready.send(Ok(()));
+
+            while let Some(event) = events.next().await {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // The task can only stop before answering if it panicked
+        block_on(started).map_err(|_| Error::Dbus(zbus::Error::Failure(String::from("The player stream stopped before starting"))))??;
+
+        Ok(Iter::new(futures::stream::unfold(receiver, async |mut receiver| {
+            receiver.recv().await.map(|event| (event, receiver))
+        })))
+    }
+}
+
+/// The blocking version of [`crate::Player`]
+#[derive(Debug, Clone)]
+pub struct Player {
+    inner: Arc<crate::Player>
+}
+impl From<Arc<crate::Player>> for Player {
+    fn from(inner: Arc<crate::Player>) -> Self {
+        Self { inner }
+    }
+}
+impl Player {
+    /// The async version, to use with [`block_on`] for the calls this doesn't mirror
+    pub fn inner(&self) -> &Arc<crate::Player> {
+        &self.inner
+    }
+
+    /// See [`crate::Player::dbus_name`]
+    pub fn dbus_name(&self) -> OwnedBusName {
+        self.inner.dbus_name()
+    }
+
+    /// See [`crate::Player::short_name`]
+    pub fn short_name(&self) -> String {
+        self.inner.short_name()
+    }
+
+    /// See [`crate::Player::display_name`]
+    pub fn display_name(&self) -> String {
+        block_on(self.inner.display_name())
+    }
+
+    /// See [`crate::Player::get`]
+    pub fn get<P>(&self, property: P) -> Result<P::Output, Error>
+    where
+        P: Property,
+        P::ParseAs: TryFrom<OwnedValue>
+    {
+        block_on(self.inner.get(property))
+    }
+
+    /// See [`crate::Player::get_or`]
+    pub fn get_or<P>(&self, property: P, default: P::Output) -> P::Output
+    where
+        P: Property,
+        P::ParseAs: TryFrom<OwnedValue>
+    {
+        block_on(self.inner.get_or(property, default))
+    }
+
+    /// See [`crate::Player::properties`]
+    pub fn properties(&self) -> PlayerProperties {
+        block_on(self.inner.properties())
+    }
+
+    /// See [`crate::Player::set`]
+    pub fn set<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), Error>
+    where
+        P: WritableProperty,
+        P::ParseAs: 'a + Into<Value<'a>>
+    {
+        block_on(self.inner.set(property, new_value))
+    }
+
+    /// See [`crate::Player::set_controlled`]
+    pub fn set_controlled<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), Error>
+    where
+        P: ControlWritableProperty,
+        P::ParseAs: 'a + Into<Value<'a>>
+    {
+        block_on(self.inner.set_controlled(property, new_value))
+    }
+
+    /// See [`crate::Player::play`]
+    pub fn play(&self) -> Result<(), Error> {
+        block_on(self.inner.play())
+    }
+
+    /// See [`crate::Player::pause`]
+    pub fn pause(&self) -> Result<(), Error> {
+        block_on(self.inner.pause())
+    }
+
+    /// See [`crate::Player::play_pause`]
+    pub fn play_pause(&self) -> Result<(), Error> {
+        block_on(self.inner.play_pause())
+    }
+
+    /// See [`crate::Player::stop`]
+    pub fn stop(&self) -> Result<(), Error> {
+        block_on(self.inner.stop())
+    }
+
+    /// See [`crate::Player::next`]
+    pub fn next(&self) -> Result<(), Error> {
+        block_on(self.inner.next())
+    }
+
+    /// See [`crate::Player::previous`]
+    pub fn previous(&self) -> Result<(), Error> {
+        block_on(self.inner.previous())
+    }
+
+    /// See [`crate::Player::seek`]
+    pub fn seek(&self, duration: Duration, backwards: bool) -> Result<(), Error> {
+        block_on(self.inner.seek(duration, backwards))
+    }
+
+    /// See [`crate::Player::seek_to`]
+    pub fn seek_to(&self, position: Duration) -> Result<(), Error> {
+        block_on(self.inner.seek_to(position))
+    }
+
+    /// See [`crate::Player::set_position`]
+    pub fn set_position(&self, track_id: impl AsRef<str>, position: Duration) -> Result<(), Error> {
+        block_on(self.inner.set_position(track_id, position))
+    }
+
+    /// See [`crate::Player::open_uri`]
+    pub fn open_uri(&self, uri: impl AsRef<str>) -> Result<(), Error> {
+        block_on(self.inner.open_uri(uri))
+    }
+
+    /// See [`crate::Player::quit`]
+    pub fn quit(&self) -> Result<(), Error> {
+        block_on(self.inner.quit())
+    }
+
+    /// See [`crate::Player::subscribe_property_change`]
+    pub fn subscribe_property_change<P>(&self, property: P) -> Result<Iter<StreamYield<P::Output>>, Error>
+    where
+        P: Property + Unpin + Send + 'static,
+        P::ParseAs: TryFrom<OwnedValue>,
+        P::Output: Send
+    {
+        block_on(self.inner.clone().subscribe_property_change(property)).map(Iter::new)
+    }
+
+    /// See [`crate::Player::subscribe`]
+    pub fn subscribe<S>(&self, signal: S) -> Result<Iter<StreamYield<S::Output>>, Error>
+    where
+        S: Signal + Unpin + Send + 'static,
+        S::ParseAs: TryFrom<OwnedValue> + Type,
+        S::Output: Send
+    {
+        block_on(self.inner.clone().subscribe(signal)).map(Iter::new)
+    }
+
+    /// See [`crate::Player::subscribe_position`]
+    pub fn subscribe_position(&self) -> Result<Iter<StreamYield<Duration>>, Error> {
+        block_on(self.inner.clone().subscribe_position()).map(Iter::new)
+    }
+
+    /// See [`crate::Player::subscribe_now_playing`]
+    pub fn subscribe_now_playing(&self) -> Result<Iter<StreamYield<NowPlaying>>, Error> {
+        block_on(self.inner.clone().subscribe_now_playing()).map(Iter::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Playback, player::mock, properties::{PlaybackStatus, Volume}};
+
+    use super::*;
+
+    #[test]
+    fn blocking_api() {
+        let bus = mock::PrivateBus::start();
+        let (player, server) = block_on(bus.connect(mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }));
+
+        let mpris = Mpris::new_from_connection(block_on(bus.client())).unwrap();
+        let players = mpris.get_players().unwrap();
+        assert_eq!(players[0].dbus_name(), player.dbus_name());
+        assert_eq!(players[0].get(PlaybackStatus).unwrap(), Playback::Playing);
+
+        // The streams are iterators
+        let mut volume = players[0].subscribe_property_change(Volume).unwrap();
+        assert_eq!(volume.next().unwrap().value, 1.0);
+        players[0].set_controlled(Volume, 0.5).unwrap();
+        assert_eq!(volume.next().unwrap().value, 0.5);
+
+        let mut events = mpris.player_stream().unwrap();
+        block_on(server.close()).unwrap();
+        assert!(matches!(events.next(), Some(PlayerEvent::Disconnected(gone)) if gone.dbus_name() == player.dbus_name()));
+    }
+}
@@ -0,0 +1,103 @@
+//! The error type of the crate
+
+use std::fmt;
+
+/// Why a call failed.
+/// <br>Errors of the bus (and the ones the player replied with) are in [`Dbus`](Self::Dbus), the rest can be matched on without looking at the messages.
+#[derive(Debug)]
+pub enum Error {
+    /// An error of the D-Bus connection, or one the player replied with
+    Dbus(zbus::Error),
+    /// The player doesn't have the property, or the interface it's on
+    UnsupportedProperty(&'static str),
+    /// The player sent the property with the wrong type, like an array as the [`Metadata`](crate::properties::Metadata). `signature` is what it sent.
+    ParseFailed { property: &'static str, signature: String },
+    /// The player is not on the bus anymore
+    PlayerGone,
+    /// The player said it can't do this, as the `capability` property (like [`CanPause`](crate::properties::CanPause)) is false
+    NotControllable { capability: &'static str },
+    /// There are no players on the bus
+    NoPlayers,
+    /// There is no current track ([`NO_TRACK`](crate::NO_TRACK))
+    NoTrack,
+    /// The trackid is empty, or not an object path
+    InvalidTrackId(String),
+    /// The length of the current track is unknown
+    UnknownLength,
+    /// The URI has no scheme, or the player doesn't support its scheme
+    UnsupportedUri(String),
+    /// The player didn't show up after being [activated](crate::Mpris::activate_player)
+    ActivationTimeout(String),
+    /// The player's introspection data is not valid
+    InvalidIntrospection(String)
+}
+impl Error {
+    /// Converts the error of reading `property`, telling apart the errors players reply with for properties they don't have
+    pub(crate) fn for_property(error: zbus::Error, property: &'static str) -> Self {
+        match &error {
+            zbus::Error::MethodError(name, _, _) if matches!(
+                name.as_str(),
+                "org.freedesktop.DBus.Error.UnknownProperty" | "org.freedesktop.DBus.Error.UnknownInterface" |
+                "org.freedesktop.DBus.Error.NotSupported" | "org.freedesktop.DBus.Error.InvalidArgs"
+            ) => Self::UnsupportedProperty(property),
+            zbus::Error::InterfaceNotFound => Self::UnsupportedProperty(property),
+            _ => error.into()
+        }
+    }
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Dbus(e) => write!(f, "{e}"),
+            Error::UnsupportedProperty(property) => write!(f, "The player doesn't support {property}"),
+            Error::ParseFailed { property, signature } => write!(f, "Malformed {property}, the player sent a `{signature}`"),
+            Error::PlayerGone => write!(f, "The player is gone"),
+            Error::NotControllable { capability } => write!(f, "The player can't do this, as {capability} is false"),
+            Error::NoPlayers => write!(f, "There are no players"),
+            Error::NoTrack => write!(f, "There is no track"),
+            Error::InvalidTrackId(trackid) => write!(f, "Invalid trackid, it's not an object path: {trackid:?}"),
+            Error::UnknownLength => write!(f, "The length of the track is unknown"),
+            Error::UnsupportedUri(uri) => write!(f, "The player can't open {uri}"),
+            Error::ActivationTimeout(name) => write!(f, "{name} did not come up after being activated"),
+            Error::InvalidIntrospection(e) => write!(f, "Invalid introspection data: {e}")
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Dbus(e) => Some(e),
+            _ => None
+        }
+    }
+}
+impl From<zbus::Error> for Error {
+    /// The errors meaning the player left the bus become [`Error::PlayerGone`]
+    fn from(error: zbus::Error) -> Self {
+        let gone = match &error {
+            zbus::Error::MethodError(name, _, _) => is_gone(name.as_str()),
+            zbus::Error::FDO(e) => matches!(**e, zbus::fdo::Error::ServiceUnknown(_) | zbus::fdo::Error::NameHasNoOwner(_)),
+            _ => false
+        };
+
+        match gone {
+            true => Error::PlayerGone,
+            false => Error::Dbus(error)
+        }
+    }
+}
+impl From<zbus::fdo::Error> for Error {
+    fn from(error: zbus::fdo::Error) -> Self {
+        zbus::Error::from(error).into()
+    }
+}
+impl From<zbus::names::Error> for Error {
+    fn from(error: zbus::names::Error) -> Self {
+        zbus::Error::from(error).into()
+    }
+}
+
+/// If the error name means the destination is not on the bus
+fn is_gone(name: &str) -> bool {
+    name == "org.freedesktop.DBus.Error.ServiceUnknown" || name == "org.freedesktop.DBus.Error.NameHasNoOwner"
+}
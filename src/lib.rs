@@ -3,14 +3,31 @@ mod player;
 pub use player::{ 
     Player, 
     Metadata, 
+    MetadataDiff,
+    PlayerDescriptor,
+    PlayerProperties,
+    PlayerState,
+    PlayerAction,
+    NO_TRACK,
+    TrackId,
+    Playlist,
     Loop, 
     Playback, 
+    PlaylistOrdering,
+    UriScheme,
+    Interface,
     properties, 
     signals, 
     streams
 };
+#[cfg(feature = "art")]
+pub use player::ArtError;
 
 mod mpris;
-pub use mpris::{ Mpris, PlayerEvent };
+pub use mpris::{ Mpris, MprisBuilder, BusKind, PlayerEvent, PlayerLifecycle, FocusEvent, ACTIVATION_TIMEOUT };
 
-pub use zbus::Error;
\ No newline at end of file
+mod error;
+pub use error::Error;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
\ No newline at end of file
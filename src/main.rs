@@ -1,7 +1,7 @@
 use std::{pin::Pin, time::Duration};
 
-use futures::{StreamExt, pin_mut, stream::select_all};
-use mpris_client_async::{Loop, Mpris, PlayerEvent, properties::*, signals::Seeked, streams::PositionStream};
+use futures::{StreamExt, stream::select_all};
+use mpris_client_async::{Mpris, properties::*, signals::Seeked, streams::PositionStream};
 
 #[tokio::main]
 async fn main() {
@@ -16,35 +16,33 @@ async fn main() {
     for player in &players {
         println!("Player: {}, with identity {} with desktop entry {}", 
             player.dbus_name(),
-            player.get(Identity).await.unwrap_or("??".to_string()),
-            player.get(DesktopEntry).await.unwrap_or("??".to_string())
+            player.get_or(Identity, "??".to_string()).await,
+            player.get_or(DesktopEntry, "??".to_string()).await
         );
 
-        println!("Player identity: {}", player.get(Identity).await.unwrap_or(String::from("??")));
+        println!("Player identity: {}", player.get_or(Identity, String::from("??")).await);
 
         println!("\tMediaPlayer2:");
         println!("\t\tCapabilities:");
-        println!("\t\t\tcan quit? {}",                  player.get(CanQuit).await.unwrap_or(false));
-        println!("\t\t\tcan set fullscreen? {}",        player.get(CanSetFullscreen).await.unwrap_or(false));
-        println!("\t\t\tcan raise? {}",                 player.get(CanRaise).await.unwrap_or(false));
-        println!("\t\t\thas track list? {}",            player.get(HasTrackList).await.unwrap_or(false));
-        println!("\t\t\tsupported URI: {:?}",           player.get(SupportedURIs).await.unwrap_or(vec![]));
-        println!("\t\t\tsupported MIME types: {:?}",    player.get(SupportedMIMEs).await.unwrap_or(vec![]));
-
-        // if player.get(CanSetFullscreen).await.unwrap_or(false) {
-        //     player.set(Fullscreen, !player.get(Fullscreen).await.unwrap_or(false)).await.expect("Failed to set fullscreen.");
-        // }
+        println!("\t\t\tcan quit? {}",                  player.get_or(CanQuit, false).await);
+        println!("\t\t\tcan set fullscreen? {}",        player.get_or(CanSetFullscreen, false).await);
+        println!("\t\t\tcan raise? {}",                 player.get_or(CanRaise, false).await);
+        println!("\t\t\thas track list? {}",            player.get_or(HasTrackList, false).await);
+        println!("\t\t\tsupported URI: {:?}",           player.get_or(SupportedURIs, vec![]).await);
+        println!("\t\t\tsupported MIME types: {:?}",    player.get_or(SupportedMIMEs, vec![]).await);
+
+        // player.toggle_fullscreen().await.expect("Failed to toggle fullscreen.");
 
         println!("\tMediaPlayer2.Player:");
-        println!("\t\tPlaybackStatus: {}",              player.get(PlaybackStatus).await.unwrap_or(mpris_client_async::Playback::Stopped));
-        println!("\t\tLoopStatus: {}",                  player.get(LoopStatus).await.unwrap_or(mpris_client_async::Loop::None));
-        println!("\t\trate: {}",                        player.get(Rate).await.unwrap_or(1.0));
+        println!("\t\tPlaybackStatus: {}",              player.get_or(PlaybackStatus, mpris_client_async::Playback::Stopped).await);
+        println!("\t\tLoopStatus: {}",                  player.get_or(LoopStatus, mpris_client_async::Loop::None).await);
+        println!("\t\trate: {}",                        player.get_or(Rate, 1.0).await);
         println!("\t\tmax rate: {:?}, min rate: {:?}",  player.get(MinimumRate).await, player.get(MaximumRate).await);
-        println!("\t\tis shuffled: {}",                 player.get(Shuffle).await.unwrap_or(false));
-        println!("\t\tvolume: {}",                      player.get(Volume).await.unwrap_or(0.0));
-        println!("\t\tPosition (in secs): {}",          player.get(Position).await.unwrap_or(Duration::from_secs(0)).as_secs());
-        println!("\t\tcan_seek: {}",                    player.get(CanSeek).await.unwrap_or(false));
-        println!("\t\tcan_control: {}",                 player.get(CanControl).await.unwrap_or(false));
+        println!("\t\tis shuffled: {}",                 player.get_or(Shuffle, false).await);
+        println!("\t\tvolume: {}",                      player.get_or(Volume, 0.0).await);
+        println!("\t\tPosition (in secs): {}",          player.get_or(Position, Duration::from_secs(0)).await.as_secs());
+        println!("\t\tcan_seek: {}",                    player.get_or(CanSeek, false).await);
+        println!("\t\tcan_control: {}",                 player.get_or(CanControl, false).await);
 
         println!("\t\t\tMetadata: {:#?}",               player.get(Metadata).await);
 
@@ -59,7 +57,7 @@ async fn main() {
         //     println!("set rate result: {:?}", result);
         // }
         // let path_to_media = String::from("file:///home/USER/definitely_not_hentai.mp3");
-        // if can_control && player.get(SupportedURIs).await.unwrap_or(vec![]).contains(&String::from("file")) {
+        // if can_control && player.supports_scheme("file").await {
         //     player.open_uri(path_to_media).await.expect("Failed to open peak");
         // }
 
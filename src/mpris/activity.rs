@@ -0,0 +1,169 @@
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+use tokio::sync::oneshot;
+use futures::{StreamExt as _, future::{AbortHandle, join_all}, stream::{self, Stream, SelectAll, abortable}};
+use zbus::names::OwnedBusName;
+
+use crate::{Error, Playback, Player, PlayerEvent, properties::{Metadata, PlaybackStatus}};
+
+use super::Mpris;
+
+/// The players, and the last time they did something (if ever since the tracking started)
+pub(super) type Activity = HashMap<OwnedBusName, (Arc<Player>, Option<Instant>)>;
+
+/// Streams yielding the name of the player every time it does something
+type ActivityStreams = SelectAll<stream::Abortable<std::pin::Pin<Box<dyn Stream<Item = OwnedBusName> + Send>>>>;
+
+impl Mpris<'_> {
+    /// Starts tracking when each player was last active, for [`most_recent_player`](Self::most_recent_player).
+    /// <br>A player counts as active when it appears on the bus, or when its [`PlaybackStatus`] or [`Metadata`] changes.
+    /// Players that are already playing when this is called count as active right now.
+    /// <br><br>The tracking runs on a tokio task until every clone of this [`Mpris`] is dropped. Calling this again does nothing.
+    pub async fn track_activity(&self) -> Result<(), Error> {
+        if self.activity.lock().unwrap().is_some() {
+            return Ok(());
+        }
+
+        // A 'static copy for the task
+        let mut mpris = Mpris::new_from_connection(self.connection.clone()).await?;
+        mpris.name_filter = self.name_filter.clone();
+
+        let state = Arc::downgrade(&self.activity);
+        let (ready, started) = oneshot::channel();
+
+        tokio::spawn(async move {
+            // Subscribe first to not miss anything while reading the players
+            let events = match mpris.player_stream().await {
+                Ok(events) => events,
+                Err(e) => {
+                    _ = ready.send(Err(e));
+                    return;
+                }
+            };
+            futures::pin_mut!(events);
+
+            let players = match mpris.get_players().await {
+                Ok(players) => players,
+                Err(e) => {
+                    _ = ready.send(Err(e));
+                    return;
+                }
+            };
+            let statuses = join_all(players.iter().map(|player| player.get(PlaybackStatus))).await;
+
+            let now = Instant::now();
+            let mut activity = Activity::new();
+            let mut streams = ActivityStreams::new();
+            let mut handles = HashMap::new();
+
+            for (player, status) in players.into_iter().zip(statuses) {
+                watch(&player, &mut streams, &mut handles).await;
+
+                let last_active = matches!(status, Ok(Playback::Playing)).then_some(now);
+                activity.insert(player.dbus_name(), (player, last_active));
+            }
+
+            match state.upgrade() {
+                Some(state) => *state.lock().unwrap() = Some(activity),
+                None => return
+            }
+            _ = ready.send(Ok(()));
+
+            loop {
+                tokio::select! {
+                    event = events.next() => {
+                        let Some(event) = event else { break };
+
+                        match event {
+                            PlayerEvent::Connected(player) | PlayerEvent::Reconnected(player) => {
+                                if let Some(handle) = handles.remove(&player.dbus_name()) {
+                                    handle.abort();
+                                }
+
+                                watch(&player, &mut streams, &mut handles).await;
+
+                                let Some(state) = state.upgrade() else { break };
+                                if let Some(activity) = state.lock().unwrap().as_mut() {
+                                    activity.insert(player.dbus_name(), (player, Some(Instant::now())));
+                                }
+                            },
+                            PlayerEvent::Disconnected(player) => {
+                                if let Some(handle) = handles.remove(&player.dbus_name()) {
+                                    handle.abort();
+                                }
+
+                                let Some(state) = state.upgrade() else { break };
+                                if let Some(activity) = state.lock().unwrap().as_mut() {
+                                    activity.remove(&player.dbus_name());
+                                }
+                            }
+                        }
+                    },
+                    Some(name) = streams.next() => {
+                        let Some(state) = state.upgrade() else { break };
+                        if let Some((_, last_active)) = state.lock().unwrap().as_mut().and_then(|activity| activity.get_mut(&name)) {
+                            *last_active = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+
+            // The event stream ended (the connection is gone), so the activity can't be trusted anymore
+            if let Some(state) = state.upgrade() {
+                *state.lock().unwrap() = None;
+            }
+        });
+
+        started.await.unwrap_or(Ok(()))
+    }
+
+    /// Returns the player that was active most recently, which is usually the one a "pause" media key should control.
+    /// <br>This needs [`track_activity`](Self::track_activity) to be running, otherwise it's the same as [`primary_player`](Self::primary_player).
+    pub async fn most_recent_player(&self) -> Option<Arc<Player>> {
+        let tracked = self.activity.lock().unwrap().as_ref().map(|activity| {
+            activity
+                .values()
+                .max_by_key(|(_, last_active)| *last_active)
+                .map(|(player, _)| player.clone())
+        });
+
+        match tracked {
+            Some(player) => player,
+            None => self.primary_player().await.ok()
+        }
+    }
+}
+
+/// Subscribes to the changes of the player that count as activity
+async fn watch(player: &Arc<Player>, streams: &mut ActivityStreams, handles: &mut HashMap<OwnedBusName, AbortHandle>) {
+    let Ok(playback) = player.clone().subscribe_property_change(PlaybackStatus).await else { return };
+    let Ok(metadata) = player.clone().subscribe_property_change(Metadata).await else { return };
+
+    // The first item of both is the current state, which is not a change
+    let changes: std::pin::Pin<Box<dyn Stream<Item = OwnedBusName> + Send>> = Box::pin(stream::select(
+        playback.skip(1).map(|changed| changed.player_name),
+        metadata.skip(1).map(|changed| changed.player_name),
+    ));
+
+    let (changes, handle) = abortable(changes);
+    streams.push(changes);
+    handles.insert(player.dbus_name(), handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::player::mock;
+
+    #[tokio::test]
+    async fn most_recent_player_tracks_activity() {
+        let bus = mock::PrivateBus::start();
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() };
+        let (player, _server) = bus.connect(mock).await;
+
+        let mpris = bus.mpris().await;
+        assert_eq!(mpris.most_recent_player().await.unwrap().dbus_name(), player.dbus_name());
+
+        mpris.track_activity().await.unwrap();
+        assert_eq!(mpris.most_recent_player().await.unwrap().dbus_name(), player.dbus_name());
+    }
+}
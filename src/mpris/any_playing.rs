@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use futures::stream::{self, Stream};
+
+use crate::{Error, Playback};
+
+use super::{Mpris, playback_watch::{PlaybackChange, PlaybackWatcher}};
+
+impl Mpris<'_> {
+    /// Returns a stream that yields whether any of the players is [playing](Playback::Playing), every time that changes.
+    /// <br>The first item is the current state. Players coming and going are taken into account, which makes this the natural source for a minimal "something is playing" indicator.
+    pub async fn any_playing_stream(&self) -> Result<impl Stream<Item = bool> + '_, Error> {
+        let (watcher, players) = PlaybackWatcher::new(self).await?;
+
+        let playing: HashSet<_> = players
+            .into_iter()
+            .filter(|(_, status)| *status == Some(Playback::Playing))
+            .map(|(player, _)| player.dbus_name())
+            .collect();
+
+        Ok(stream::unfold((watcher, playing, None), |(mut watcher, mut playing, mut last)| async move {
+            loop {
+                let any_playing = !playing.is_empty();
+                if last != Some(any_playing) {
+                    last = Some(any_playing);
+                    return Some((any_playing, (watcher, playing, last)));
+                }
+
+                match watcher.next().await? {
+                    PlaybackChange::Status(player, Playback::Playing) => { playing.insert(player.dbus_name()); },
+                    PlaybackChange::Status(player, _) => { playing.remove(&player.dbus_name()); },
+                    PlaybackChange::Gone(name) => { playing.remove(&name); }
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt as _;
+
+    use crate::player::mock;
+
+    #[tokio::test]
+    async fn any_playing_stream() {
+        let bus = mock::PrivateBus::start();
+        let (_player, server) = bus.connect(mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }).await;
+
+        let mpris = bus.mpris().await;
+        let any_playing = mpris.any_playing_stream().await.unwrap();
+        futures::pin_mut!(any_playing);
+
+        assert!(any_playing.next().await.unwrap());
+
+        server.close().await.unwrap();
+        let changed = tokio::time::timeout(Duration::from_secs(2), any_playing.next()).await.unwrap();
+        assert_eq!(changed, Some(false));
+    }
+}
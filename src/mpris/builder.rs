@@ -0,0 +1,93 @@
+use std::{fmt, time::Duration};
+
+use zbus::connection;
+
+use crate::Error;
+
+use super::Mpris;
+
+/// Which bus an [`Mpris`] is connected to, see [`Mpris::bus_kind`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BusKind {
+    #[default]
+    Session,
+    System,
+    /// A bus at the given [D-Bus address](https://dbus.freedesktop.org/doc/dbus-specification.html#addresses)
+    Address(String),
+    /// A connection given to [`Mpris::new_from_connection`], which can be to any bus
+    Unknown
+}
+impl fmt::Display for BusKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusKind::Session => write!(f, "session bus"),
+            BusKind::System => write!(f, "system bus"),
+            BusKind::Address(address) => write!(f, "bus at {address}"),
+            BusKind::Unknown => write!(f, "unknown bus")
+        }
+    }
+}
+
+/// Configures an [`Mpris`] up front, see [`Mpris::builder`].
+/// <br>By default it connects to the session bus, without a method timeout and without any extra name filtering.
+#[derive(Debug, Clone, Default)]
+pub struct MprisBuilder {
+    bus: BusKind,
+    timeout: Option<Duration>,
+    name_filter: Option<String>
+}
+impl MprisBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect to the session bus. This is the default.
+    pub fn session(mut self) -> Self {
+        self.bus = BusKind::Session;
+        self
+    }
+
+    /// Connect to the system bus
+    pub fn system(mut self) -> Self {
+        self.bus = BusKind::System;
+        self
+    }
+
+    /// Connect to a bus at the given [D-Bus address](https://dbus.freedesktop.org/doc/dbus-specification.html#addresses), for example `unix:path=/run/user/1000/bus`
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.bus = BusKind::Address(address.into());
+        self
+    }
+
+    /// The maximum time to wait for the reply of a method call (this includes reading and setting properties) on the connection.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Only players whose bus name starts with `prefix` will be returned, for example `org.mpris.MediaPlayer2.spotify`.
+    /// <br>Names not starting with `org.mpris.MediaPlayer2` are never returned, regardless of this.
+    pub fn name_filter(mut self, prefix: impl Into<String>) -> Self {
+        self.name_filter = Some(prefix.into());
+        self
+    }
+
+    /// Connects to the bus
+    pub async fn build<'a>(self) -> Result<Mpris<'a>, Error> {
+        let builder = match &self.bus {
+            BusKind::Session | BusKind::Unknown => connection::Builder::session()?,
+            BusKind::System => connection::Builder::system()?,
+            BusKind::Address(address) => connection::Builder::address(address.as_str())?
+        };
+
+        let builder = match self.timeout {
+            Some(timeout) => builder.method_timeout(timeout),
+            None => builder
+        };
+
+        let mut mpris = Mpris::with_bus(builder.build().await?, self.bus).await?;
+        mpris.name_filter = self.name_filter;
+
+        Ok(mpris)
+    }
+}
@@ -0,0 +1,106 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::stream::{self, Stream};
+use zbus::names::OwnedBusName;
+
+use crate::{Error, Playback, Player};
+
+use super::{Mpris, playback_watch::{PlaybackChange, PlaybackWatcher}};
+
+/// A reason to change the focused player, yielded by [`Mpris::focus_stream`]
+#[derive(Debug, Clone)]
+pub enum FocusEvent {
+    /// The focused player left the bus, so nothing is focused anymore
+    FocusedGone(OwnedBusName),
+    /// A player other than the focused one started playing, so it probably should be focused instead
+    OtherStartedPlaying(Arc<Player>),
+}
+
+impl Mpris<'_> {
+    /// Sets the player the controls of a multi-player UI act on. See [`focus_stream`](Self::focus_stream) for when it should change.
+    /// <br>The focus is shared by every clone of this [`Mpris`].
+    pub fn set_focus(&self, player: &Player) {
+        *self.focus.lock().unwrap() = Some(player.dbus_name());
+    }
+
+    /// Clears the focus set by [`set_focus`](Self::set_focus)
+    pub fn clear_focus(&self) {
+        *self.focus.lock().unwrap() = None;
+    }
+
+    /// Returns the name of the focused player, if there is one. See [`set_focus`](Self::set_focus).
+    pub fn focused(&self) -> Option<OwnedBusName> {
+        self.focus.lock().unwrap().clone()
+    }
+
+    /// Returns a stream of [`FocusEvent`]s, telling when the focus should change: when the focused player quits, or when another one starts playing.
+    /// <br>The focus is cleared automatically when the focused player leaves, but otherwise it's up to the consumer to follow the suggestions with [`set_focus`](Self::set_focus).
+    pub async fn focus_stream(&self) -> Result<impl Stream<Item = FocusEvent> + '_, Error> {
+        let (watcher, players) = PlaybackWatcher::new(self).await?;
+
+        // Only a change to Playing counts, not a player that already played when this was called
+        let statuses: HashMap<OwnedBusName, Option<Playback>> = players
+            .into_iter()
+            .map(|(player, status)| (player.dbus_name(), status))
+            .collect();
+
+        Ok(stream::unfold((watcher, statuses), move |(mut watcher, mut statuses)| async move {
+            loop {
+                match watcher.next().await? {
+                    PlaybackChange::Gone(name) => {
+                        statuses.remove(&name);
+
+                        let mut focus = self.focus.lock().unwrap();
+                        if focus.as_ref() == Some(&name) {
+                            *focus = None;
+                            drop(focus);
+                            return Some((FocusEvent::FocusedGone(name), (watcher, statuses)));
+                        }
+                    },
+                    PlaybackChange::Status(player, status) => {
+                        let previous = statuses.insert(player.dbus_name(), Some(status)).flatten();
+                        let started = status == Playback::Playing && previous != Some(Playback::Playing);
+
+                        if started && self.focused() != Some(player.dbus_name()) {
+                            return Some((FocusEvent::OtherStartedPlaying(player), (watcher, statuses)));
+                        }
+                    }
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt as _;
+
+    use crate::{FocusEvent, player::mock};
+
+    #[tokio::test]
+    async fn focus_stream() {
+        let bus = mock::PrivateBus::start();
+        let (focused, focused_server) = bus.connect_as("org.mpris.MediaPlayer2.a", mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }).await;
+        let (_other, other_server) = bus.connect_as("org.mpris.MediaPlayer2.b", mock::MockPlayer::default()).await;
+
+        let mpris = bus.mpris().await;
+        mpris.set_focus(&focused);
+
+        let focus = mpris.focus_stream().await.unwrap();
+        futures::pin_mut!(focus);
+
+        let iface = other_server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.playback_status = String::from("Playing");
+        iface.get().await.playback_status_changed(iface.signal_emitter()).await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(2), focus.next()).await.unwrap().unwrap();
+        assert!(matches!(event, FocusEvent::OtherStartedPlaying(player) if player.short_name() == "b"));
+
+        focused_server.close().await.unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(2), focus.next()).await.unwrap().unwrap();
+        assert!(matches!(event, FocusEvent::FocusedGone(name) if name == focused.dbus_name()));
+        assert_eq!(mpris.focused(), None);
+    }
+}
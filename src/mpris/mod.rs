@@ -1,71 +1,325 @@
-use std::sync::Arc;
+use std::{sync::{Arc, Mutex}, time::Duration};
 
-use futures::future::join_all;
+use futures::{StreamExt as _, future::join_all};
 
 use zbus::{Connection, fdo::DBusProxy};
 
-use crate::Player;
+use crate::{Error, Playback, Player, properties::{CanPause, Identity, PlaybackStatus}};
 
 mod player_stream;
-pub use player_stream::PlayerEvent;
+pub use player_stream::{PlayerEvent, PlayerLifecycle};
+
+mod builder;
+pub use builder::{BusKind, MprisBuilder};
+
+mod activity;
+
+mod playback_watch;
+
+mod any_playing;
+
+mod focus;
+pub use focus::FocusEvent;
+
+/// The prefix of every MPRIS player's bus name
+pub(crate) const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2";
+
+/// How long [`Mpris::activate_player`] waits for the player to show up. The same as the bus' default activation timeout.
+pub const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(25);
 
 #[derive(Debug, Clone)]
 /// Provides a convenient way to connect to the dbus and retrieve the MPRIS players.
 pub struct Mpris<'a> {
     connection: Connection,
-    pub(crate) proxy: DBusProxy<'a>
+    /// Which bus the connection is to
+    bus: BusKind,
+    pub(crate) proxy: DBusProxy<'a>,
+    /// Set by [`MprisBuilder::name_filter`]
+    name_filter: Option<String>,
+    /// Only set while [`track_activity`](Self::track_activity) is running
+    activity: Arc<Mutex<Option<activity::Activity>>>,
+    /// Set by [`set_focus`](Self::set_focus)
+    focus: Arc<Mutex<Option<zbus::names::OwnedBusName>>>
 }
 
 impl<'a> Mpris<'a> {
-    /// Creates a new connection
-    pub async fn new() -> Result<Self, zbus::Error> {
-        let connection = Connection::session().await?;
-        let proxy = zbus::fdo::DBusProxy::new(&connection).await?;
+    /// Creates a new connection to the session bus, where players usually are
+    pub async fn new() -> Result<Self, Error> {
+        Self::with_bus(Connection::session().await?, BusKind::Session).await
+    }
 
-        Ok(
-            Self {
-                connection,
-                proxy
-            }
-        )
+    /// Creates a new connection to the system bus, where players of some embedded and kiosk setups are
+    pub async fn new_system() -> Result<Self, Error> {
+        Self::with_bus(Connection::system().await?, BusKind::System).await
+    }
+
+    /// Creates a new instance from an already existing connection. As that can be to any bus, its [`bus_kind`](Self::bus_kind) is [`BusKind::Unknown`].
+    pub async fn new_from_connection(connection: Connection) -> Result<Self, Error> {
+        Self::with_bus(connection, BusKind::Unknown).await
     }
 
-    /// Creates a new instance from an already existing connection
-    pub async fn new_from_connection(connection: Connection) -> Result<Self, zbus::Error> {
+    pub(crate) async fn with_bus(connection: Connection, bus: BusKind) -> Result<Self, Error> {
         let proxy = zbus::fdo::DBusProxy::new(&connection).await?;
 
         Ok(
             Self {
                 connection,
-                proxy
+                bus,
+                proxy,
+                name_filter: None,
+                activity: Arc::default(),
+                focus: Arc::default()
             }
         )
     }
 
+    /// Returns an [`MprisBuilder`] to configure the connection (bus, timeout, name filter) up front.
+    pub fn builder() -> MprisBuilder {
+        MprisBuilder::new()
+    }
+
+    /// Returns which bus this is connected to
+    pub fn bus_kind(&self) -> &BusKind {
+        &self.bus
+    }
+
     /// Returns a copy of the underlying connection
     pub fn connection(&self) -> Connection {
         self.connection.clone()
     }
 
     /// Gets all currently available players.
-    pub async fn get_players(&self) -> Result<Vec<Arc<Player>>, zbus::Error> {
+    pub async fn get_players(&self) -> Result<Vec<Arc<Player>>, Error> {
+        self.players(false).await
+    }
+
+    /// Like [`get_players`](Self::get_players), but the players are created with [`Player::new_lazy`], which is faster for many players.
+    /// <br>Useful for dashboards that only read a few properties once.
+    pub async fn get_players_fast(&self) -> Result<Vec<Arc<Player>>, Error> {
+        self.players(true).await
+    }
+
+    async fn players(&self, lazy: bool) -> Result<Vec<Arc<Player>>, Error> {
         let names = self.proxy.list_names().await?;
 
-        Ok (
-            join_all(names   
-                    .iter()
-                    .filter(|name| name.starts_with("org.mpris.MediaPlayer2"))
-                    .map (async |name| Player::new(name.clone(), self.connection.clone()).await)
-                )
-            .await
+        join_all(names   
+                .iter()
+                .filter(|name| is_wanted_name(name, self.name_filter.as_deref()))
+                .map (async |name| match lazy {
+                    true => Player::new_lazy(name.clone(), self.connection.clone()).await,
+                    false => Player::new(name.clone(), self.connection.clone()).await
+                })
+            )
+        .await
+        .into_iter()
+        .try_fold(Vec::new(), |mut vec, player| match player {
+            Ok(v) => { 
+                vec.push(Arc::new(v));
+                Ok(vec)   
+            },
+            Err(e) => Err(e)
+        })
+    }
+
+    /// Returns the player to control when only one is wanted: the first [playing](Playback::Playing) one, or the first one if none is playing.
+    /// <br>Fails if there are no players at all, so unlike indexing into [`get_players`](Self::get_players), this can be used with `?`.
+    pub async fn primary_player(&self) -> Result<Arc<Player>, Error> {
+        let players = self.get_players().await?;
+        let statuses = join_all(players.iter().map(|player| player.get(PlaybackStatus))).await;
+
+        let playing = statuses.iter().position(|status| matches!(status, Ok(Playback::Playing)));
+        players
             .into_iter()
-            .try_fold(Vec::new(), |mut vec, player| match player {
-                Ok(v) => { 
-                    vec.push(Arc::new(v));
-                    Ok(vec)   
-                },
-                Err(e) => return Err(e)
-            })?
+            .nth(playing.unwrap_or(0))
+            .ok_or(Error::NoPlayers)
+    }
+
+    /// Gets the names of the players that are [D-Bus activatable](https://dbus.freedesktop.org/doc/dbus-specification.html#message-bus-starting-services), meaning they can be started through the bus.
+    /// <br>These are not necessarily running, so they are names rather than [`Player`]s. Use [`activate_player`](Self::activate_player) to start one.
+    pub async fn get_activatable_players(&self) -> Result<Vec<String>, Error> {
+        let names = self.proxy.list_activatable_names().await?;
+
+        Ok(
+            names
+                .into_iter()
+                .filter(|name| is_wanted_name(name, self.name_filter.as_deref()))
+                .map(|name| name.to_string())
+                .collect()
+        )
+    }
+
+    /// Starts an activatable player (see [`get_activatable_players`](Self::get_activatable_players)) through the bus, and returns it once it's up.
+    /// <br>If the player is already running, it's simply returned. Fails if the player doesn't show up within [`ACTIVATION_TIMEOUT`].
+    /// <br><br>`well_known_name` can be either the full bus name (`org.mpris.MediaPlayer2.vlc`) or just the part after the prefix (`vlc`).
+    pub async fn activate_player(&self, well_known_name: &str) -> Result<Arc<Player>, Error> {
+        let name = full_name(well_known_name);
+
+        // Watch first, to not miss the player coming up
+        let lifecycle = self.watch_player(&name).await?;
+        futures::pin_mut!(lifecycle);
+
+        self.proxy.start_service_by_name(name.as_str().try_into()?, 0).await?;
+
+        let online = async {
+            while let Some(state) = lifecycle.next().await {
+                if let PlayerLifecycle::Online(player) = state {
+                    return Some(player);
+                }
+            }
+            None
+        };
+
+        match tokio::time::timeout(ACTIVATION_TIMEOUT, online).await {
+            Ok(Some(player)) => Ok(player),
+            _ => Err(Error::ActivationTimeout(name.clone()))
+        }
+    }
+
+    /// Gets the players that are currently [playing](Playback::Playing).
+    /// <br>Players whose status can't be read are left out, rather than failing the whole call.
+    pub async fn playing_players(&self) -> Result<Vec<Arc<Player>>, Error> {
+        let players = self.get_players().await?;
+        let statuses = join_all(players.iter().map(|player| player.get(PlaybackStatus))).await;
+
+        Ok(
+            players
+                .into_iter()
+                .zip(statuses)
+                .filter(|(_, status)| matches!(status, Ok(Playback::Playing)))
+                .map(|(player, _)| player)
+                .collect()
         )
     }
-}
\ No newline at end of file
+
+    /// Gets all currently available players, the [playing](Playback::Playing) ones first, then the paused, and then the stopped ones.
+    /// <br>Players with the same status are ordered by their [`Identity`] (and then bus name), so the order doesn't flicker between calls.
+    /// Players whose status can't be read count as stopped.
+    pub async fn get_players_sorted_by_activity(&self) -> Result<Vec<Arc<Player>>, Error> {
+        let players = self.get_players().await?;
+        let keys = join_all(players.iter().map(async |player| {
+            let rank = match player.get(PlaybackStatus).await {
+                Ok(Playback::Playing) => 0,
+                Ok(Playback::Paused) => 1,
+                _ => 2
+            };
+            (rank, player.get_or(Identity, String::new()).await, player.dbus_name())
+        })).await;
+
+        let mut sorted: Vec<_> = keys.into_iter().zip(players).collect();
+        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(sorted.into_iter().map(|(_, player)| player).collect())
+    }
+
+    /// Pauses every [playing](Playback::Playing) player at once, like for a "pause everything" action when the screen gets locked.
+    /// <br>Players that aren't playing, or can't be paused (see [`CanPause`]) are left alone, and aren't in the result.
+    /// Returns the result for each player it tried to pause, so it's visible which ones refused.
+    pub async fn pause_all(&self) -> Result<Vec<(Arc<Player>, Result<(), Error>)>, Error> {
+        let players = self.playing_players().await?;
+        let can_pause = join_all(players.iter().map(|player| player.get_or(CanPause, true))).await;
+
+        let players: Vec<Arc<Player>> = players
+            .into_iter()
+            .zip(can_pause)
+            .filter(|(_, can_pause)| *can_pause)
+            .map(|(player, _)| player)
+            .collect();
+        let results = join_all(players.iter().map(|player| player.pause())).await;
+
+        Ok(players.into_iter().zip(results).collect())
+    }
+}
+
+/// Prepends [`MPRIS_PREFIX`] to the name, unless it's already there
+pub(crate) fn full_name(well_known_name: &str) -> String {
+    if well_known_name.starts_with(MPRIS_PREFIX) {
+        well_known_name.to_string()
+    } else {
+        format!("{MPRIS_PREFIX}.{well_known_name}")
+    }
+}
+
+/// If the bus name is an MPRIS player's, and it passes the (optional) filter
+pub(crate) fn is_wanted_name(name: &str, filter: Option<&str>) -> bool {
+    name.starts_with(MPRIS_PREFIX) && filter.is_none_or(|prefix| name.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{player::mock, properties::Rate};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_players_sorted_by_activity() {
+        let bus = mock::PrivateBus::start();
+        let (_stopped, _a) = bus.connect_as("org.mpris.MediaPlayer2.a", mock::MockPlayer::default()).await;
+        let (_playing, _b) = bus.connect_as("org.mpris.MediaPlayer2.b", mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }).await;
+        let (_paused, _c) = bus.connect_as("org.mpris.MediaPlayer2.c", mock::MockPlayer { playback_status: String::from("Paused"), ..Default::default() }).await;
+
+        let mpris = bus.mpris().await;
+        let names: Vec<String> = mpris.get_players_sorted_by_activity().await.unwrap()
+            .iter()
+            .map(|player| player.short_name())
+            .collect();
+
+        assert_eq!(names, ["b", "c", "a"]);
+    }
+
+    #[tokio::test]
+    async fn primary_player() {
+        let bus = mock::PrivateBus::start();
+        let mpris = bus.mpris().await;
+        assert!(matches!(mpris.primary_player().await, Err(Error::NoPlayers)));
+
+        let (_stopped, _a) = bus.connect_as("org.mpris.MediaPlayer2.a", mock::MockPlayer::default()).await;
+        assert_eq!(mpris.primary_player().await.unwrap().short_name(), "a");
+
+        let (_playing, _b) = bus.connect_as("org.mpris.MediaPlayer2.b", mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }).await;
+        assert_eq!(mpris.primary_player().await.unwrap().short_name(), "b");
+    }
+
+    #[tokio::test]
+    async fn pause_all() {
+        let bus = mock::PrivateBus::start();
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (_player, _server) = bus.connect(mock).await;
+
+        let mpris = bus.mpris().await;
+        let results = mpris.pause_all().await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_ok());
+        assert_eq!(*calls.lock().unwrap(), vec!["Pause"]);
+
+        // Nothing is playing anymore
+        assert!(mpris.pause_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn bus_kind() {
+        let bus = mock::PrivateBus::start();
+        let (player, _server) = bus.connect(mock::MockPlayer::default()).await;
+
+        let mpris = Mpris::builder().address(bus.address.as_str()).build().await.unwrap();
+        assert_eq!(mpris.bus_kind(), &BusKind::Address(bus.address.clone()));
+        assert_eq!(mpris.get_players().await.unwrap()[0].dbus_name(), player.dbus_name());
+
+        let mpris = bus.mpris().await;
+        assert_eq!(mpris.bus_kind(), &BusKind::Unknown);
+        assert_eq!(BusKind::System.to_string(), "system bus");
+    }
+
+    #[tokio::test]
+    async fn get_players_fast() {
+        let bus = mock::PrivateBus::start();
+        let (_player, _server) = bus.connect(mock::MockPlayer { rate: 2.0, ..Default::default() }).await;
+
+        let mpris = bus.mpris().await;
+        let players = mpris.get_players_fast().await.unwrap();
+
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].get(Rate).await.unwrap(), 2.0);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::{StreamExt as _, future::{AbortHandle, join_all}, stream::{Abortable, BoxStream, SelectAll, abortable}};
+use zbus::names::OwnedBusName;
+
+use crate::{Error, Playback, Player, PlayerEvent, properties::PlaybackStatus};
+
+use super::Mpris;
+
+/// Something that happened to one of the players, yielded by [`PlaybackWatcher::next`]
+pub(super) enum PlaybackChange {
+    /// The [`PlaybackStatus`] of the player changed. For players that just appeared, this is the current one.
+    Status(Arc<Player>, Playback),
+    /// The player left the bus
+    Gone(OwnedBusName),
+}
+
+/// The statuses of the watched players, each abortable on its own
+type StatusStreams = SelectAll<Abortable<BoxStream<'static, (Arc<Player>, Playback)>>>;
+
+/// Follows the [`PlaybackStatus`] of every player, including the ones coming and going.
+/// <br>The shared core of the streams that are about all players' playback, like [`Mpris::any_playing_stream`].
+pub(super) struct PlaybackWatcher<'a> {
+    events: BoxStream<'a, PlayerEvent>,
+    statuses: StatusStreams,
+    handles: HashMap<OwnedBusName, AbortHandle>,
+}
+impl<'a> PlaybackWatcher<'a> {
+    /// Starts watching, and returns the current players with their statuses (`None` if it couldn't be read)
+    pub async fn new(mpris: &'a Mpris<'_>) -> Result<(Self, Vec<(Arc<Player>, Option<Playback>)>), Error> {
+        // Subscribe first to not miss anything while reading the players
+        let events = mpris.player_stream().await?.boxed();
+
+        let players = mpris.get_players().await?;
+        let statuses = join_all(players.iter().map(|player| player.get(PlaybackStatus))).await;
+
+        let mut watcher = Self {
+            events,
+            statuses: StatusStreams::new(),
+            handles: HashMap::new(),
+        };
+
+        for player in &players {
+            watcher.watch(player.clone()).await;
+        }
+
+        Ok((watcher, players.into_iter().zip(statuses.into_iter().map(Result::ok)).collect()))
+    }
+
+    /// Waits for the next change. Returns `None` once the connection is gone.
+    /// <br>The first status of every watched player is its current one, which may not be a change for the players that were there from the start.
+    pub async fn next(&mut self) -> Option<PlaybackChange> {
+        loop {
+            tokio::select! {
+                event = self.events.next() => match event? {
+                    PlayerEvent::Connected(player) | PlayerEvent::Reconnected(player) => self.watch(player).await,
+                    PlayerEvent::Disconnected(player) => {
+                        self.unwatch(&player.dbus_name());
+                        return Some(PlaybackChange::Gone(player.dbus_name()));
+                    }
+                },
+                Some((player, status)) = self.statuses.next() => return Some(PlaybackChange::Status(player, status))
+            }
+        }
+    }
+
+    /// Subscribes to the [`PlaybackStatus`] of the player, replacing the previous subscription of the same name
+    async fn watch(&mut self, player: Arc<Player>) {
+        self.unwatch(&player.dbus_name());
+
+        let Ok(statuses) = player.clone().subscribe_property_change(PlaybackStatus).await else { return };
+        let name = player.dbus_name();
+        let statuses: BoxStream<'static, _> = statuses
+            .map(move |status| (player.clone(), status.value))
+            .boxed();
+
+        let (statuses, handle) = abortable(statuses);
+        self.statuses.push(statuses);
+        self.handles.insert(name, handle);
+    }
+
+    /// Stops the subscription of the player, if there is one
+    fn unwatch(&mut self, name: &OwnedBusName) {
+        if let Some(handle) = self.handles.remove(name) {
+            handle.abort();
+        }
+    }
+}
@@ -1,11 +1,12 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use futures::stream::{self, Stream, StreamExt as _};
+use futures::{future, stream::{self, Stream, StreamExt as _}};
+use tokio::time::Instant;
 use zbus::names::OwnedBusName;
 
-use crate::Player;
+use crate::{Error, Player};
 
-use super::Mpris;
+use super::{Mpris, full_name, is_wanted_name};
 
 // The contents of this file was vibecoded, as it seemed boring :)
 
@@ -19,6 +20,18 @@ pub enum PlayerEvent {
     /// method calls on it will now fail, but its metadata (name, etc.) is
     /// still readable.
     Disconnected(Arc<Player>),
+    /// A player left the bus, and came back quickly (as it does when restarted). The `Arc<Player>` is the new handle.
+    /// <br>Only yielded by [`Mpris::player_stream_debounced`], instead of a [`Disconnected`](Self::Disconnected) and [`Connected`](Self::Connected) pair.
+    Reconnected(Arc<Player>),
+}
+
+/// The state of a player watched by name, see [`Mpris::watch_player`].
+#[derive(Debug, Clone)]
+pub enum PlayerLifecycle {
+    /// The player is on the bus. This is a fresh handle every time the player (re)appears.
+    Online(Arc<Player>),
+    /// The player is not on the bus (anymore).
+    Offline,
 }
 
 impl Mpris<'_> {
@@ -33,21 +46,27 @@ impl Mpris<'_> {
     /// # Example
     ///
     /// ```rust,no_run
-    /// use futures::StreamExt as _;
+    /// use futures::{StreamExt as _, pin_mut};
+    /// use mpris_client_async::{Mpris, PlayerEvent};
     ///
+    /// # async fn run() -> Result<(), mpris_client_async::Error> {
     /// let mpris = Mpris::new().await?;
-    /// let mut events = mpris.player_stream().await?;
+    /// let events = mpris.player_stream().await?;
+    /// pin_mut!(events);
     ///
     /// while let Some(event) = events.next().await {
     ///     match event {
-    ///         PlayerEvent::Connected(player)    => println!("+ {}", player.name()),
-    ///         PlayerEvent::Disconnected(player) => println!("- {}", player.name()),
+    ///         PlayerEvent::Connected(player)    => println!("+ {}", player.dbus_name()),
+    ///         PlayerEvent::Disconnected(player) => println!("- {}", player.dbus_name()),
+    ///         PlayerEvent::Reconnected(player)  => println!("~ {}", player.dbus_name()),
     ///     }
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn player_stream(
         &self,
-    ) -> Result<impl Stream<Item = PlayerEvent>, zbus::Error> {
+    ) -> Result<impl Stream<Item = PlayerEvent>, Error> {
         // Subscribe first to not miss the first while awawiting for get_players
         let signal_stream = self.proxy.receive_name_owner_changed().await?;
 
@@ -59,10 +78,11 @@ impl Mpris<'_> {
             .collect();
 
         let connection = self.connection.clone();
+        let name_filter = self.name_filter.clone();
 
         let s = stream::unfold(
-            (signal_stream, known, connection),
-            |(mut signal_stream, mut known, connection)| async move {
+            (signal_stream, known, connection, name_filter),
+            |(mut signal_stream, mut known, connection, name_filter)| async move {
                 // Loop until we find an event we actually want to surface.
                 loop {
                     // If the underlying signal stream ends the bus is gone.
@@ -75,7 +95,7 @@ impl Mpris<'_> {
 
                     // Only care about MPRIS names.
                     let name = args.name.to_string();
-                    if !name.starts_with("org.mpris.MediaPlayer2") {
+                    if !is_wanted_name(&name, name_filter.as_deref()) {
                         continue;
                     }
 
@@ -95,7 +115,7 @@ impl Mpris<'_> {
                                 Ok(player) => {
                                     let player = Arc::new(player);
                                     known.insert(bus_name, player.clone());
-                                    let state = (signal_stream, known, connection);
+                                    let state = (signal_stream, known, connection, name_filter);
                                     return Some((PlayerEvent::Connected(player), state));
                                 }
                                 Err(_) => continue,
@@ -112,7 +132,7 @@ impl Mpris<'_> {
                             // Return the Arc we were holding so the caller
                             // can still read its metadata.
                             if let Some(player) = known.remove(&bus_name) {
-                                let state = (signal_stream, known, connection);
+                                let state = (signal_stream, known, connection, name_filter);
                                 return Some((PlayerEvent::Disconnected(player), state));
                             }
                             // Unknown player left (wasn't in our snapshot) — skip.
@@ -128,4 +148,119 @@ impl Mpris<'_> {
 
         Ok(s)
     }
-}
\ No newline at end of file
+
+    /// Like [`player_stream`](Self::player_stream), but a player that disconnects and connects again within `window` yields a single
+    /// [`PlayerEvent::Reconnected`], rather than a [`Disconnected`](PlayerEvent::Disconnected) and a [`Connected`](PlayerEvent::Connected).
+    /// <br>This avoids the flicker of a UI when a player restarts. The price is that every disconnect is yielded `window` later.
+    /// Something like 500 milliseconds is usually enough.
+    pub async fn player_stream_debounced(
+        &self,
+        window: Duration,
+    ) -> Result<impl Stream<Item = PlayerEvent>, Error> {
+        let events = Box::pin(self.player_stream().await?);
+        // The disconnected players, waiting to see if they come back before their deadline
+        let pending: HashMap<OwnedBusName, (Arc<Player>, Instant)> = HashMap::new();
+
+        let s = stream::unfold(
+            (Some(events), pending),
+            move |(mut events, mut pending)| async move {
+                loop {
+                    // Once the events ended, only the pending disconnects are left to yield
+                    let Some(inner) = events.as_mut() else {
+                        let name = pending.keys().next()?.clone();
+                        let (player, _) = pending.remove(&name)?;
+                        return Some((PlayerEvent::Disconnected(player), (events, pending)));
+                    };
+
+                    let deadline = pending.values().map(|(_, deadline)| *deadline).min();
+
+                    tokio::select! {
+                        event = inner.next() => match event {
+                            None => events = None,
+                            Some(PlayerEvent::Disconnected(player)) => {
+                                pending.insert(player.dbus_name(), (player, Instant::now() + window));
+                            },
+                            Some(PlayerEvent::Connected(player)) => {
+                                let event = match pending.remove(&player.dbus_name()) {
+                                    Some(_) => PlayerEvent::Reconnected(player),
+                                    None => PlayerEvent::Connected(player)
+                                };
+                                return Some((event, (events, pending)));
+                            },
+                            Some(event) => return Some((event, (events, pending))),
+                        },
+                        _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                            let now = Instant::now();
+                            let expired = pending.iter().find(|(_, (_, deadline))| *deadline <= now).map(|(name, _)| name.clone());
+
+                            if let Some((player, _)) = expired.and_then(|name| pending.remove(&name)) {
+                                return Some((PlayerEvent::Disconnected(player), (events, pending)));
+                            }
+                        }
+                    }
+                }
+            },
+        );
+
+        Ok(s)
+    }
+
+    /// Watches a single player by its name across restarts, yielding a [`PlayerLifecycle`] every time it comes online or goes offline.
+    /// <br>The first item is always the current state. Subscriptions made on a player die with it, so re-subscribe on every [`PlayerLifecycle::Online`].
+    ///
+    /// <br><br>`well_known_name` can be either the full bus name (`org.mpris.MediaPlayer2.spotify`) or just the part after the prefix (`spotify`).
+    pub async fn watch_player(
+        &self,
+        well_known_name: &str,
+    ) -> Result<impl Stream<Item = PlayerLifecycle>, Error> {
+        let name = full_name(well_known_name);
+
+        // Subscribe before reading the current state, to not miss anything in between
+        let events = self.player_stream().await?;
+
+        let initial = match self.get_players().await?.into_iter().find(|p| p.dbus_name().as_str() == name) {
+            Some(player) => PlayerLifecycle::Online(player),
+            None => PlayerLifecycle::Offline,
+        };
+
+        let changes = events.filter_map(move |event| {
+            future::ready(match event {
+                PlayerEvent::Connected(player) if player.dbus_name().as_str() == name => Some(PlayerLifecycle::Online(player)),
+                PlayerEvent::Disconnected(player) if player.dbus_name().as_str() == name => Some(PlayerLifecycle::Offline),
+                _ => None,
+            })
+        });
+
+        Ok(stream::once(future::ready(initial)).chain(changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::StreamExt as _;
+
+    use crate::{PlayerEvent, player::mock};
+
+    #[tokio::test]
+    async fn debounced_player_stream() {
+        let bus = mock::PrivateBus::start();
+        let (_player, server) = bus.connect(mock::MockPlayer::default()).await;
+
+        let mpris = bus.mpris().await;
+        let events = mpris.player_stream_debounced(Duration::from_millis(500)).await.unwrap();
+        futures::pin_mut!(events);
+
+        // Restart
+        server.close().await.unwrap();
+        let (_, server) = bus.connect(mock::MockPlayer::default()).await;
+        let event = tokio::time::timeout(Duration::from_secs(2), events.next()).await.unwrap().unwrap();
+        assert!(matches!(event, PlayerEvent::Reconnected(_)));
+
+        // Quit
+        server.close().await.unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(2), events.next()).await.unwrap().unwrap();
+        assert!(matches!(event, PlayerEvent::Disconnected(_)));
+    }
+}
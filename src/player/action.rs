@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// A step of [`Player::apply`](super::Player::apply)
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerAction {
+    /// See [`Player::open_uri`](super::Player::open_uri). The next action waits until the player loads the track.
+    OpenUri(String),
+    /// See [`Player::seek_to`](super::Player::seek_to)
+    SeekTo(Duration),
+    /// Sets [`Volume`](super::properties::Volume)
+    SetVolume(f64),
+    /// Sets [`Rate`](super::properties::Rate)
+    SetRate(f64),
+    Play,
+    Pause,
+    Stop,
+    Next,
+    Previous,
+}
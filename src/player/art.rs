@@ -0,0 +1,108 @@
+//! Loading the album art of a [`Metadata`], behind the `art` feature
+
+use std::fmt;
+
+use base64::Engine as _;
+use reqwest::Url;
+
+use super::Metadata;
+
+/// Why [`Metadata::load_art`] failed
+#[derive(Debug)]
+pub enum ArtError {
+    /// The metadata has no [`art_url`](Metadata::art_url)
+    NoArt,
+    /// The art URL is not a valid URL
+    InvalidUrl(String),
+    /// The art URL's scheme is not `file`, `http`, `https` or `data`
+    UnsupportedScheme(String),
+    /// The local file couldn't be read
+    Io(std::io::Error),
+    /// The remote art couldn't be fetched
+    Http(reqwest::Error),
+    /// The `data:` URI is malformed, or its base64 is invalid
+    InvalidData
+}
+impl fmt::Display for ArtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtError::NoArt => write!(f, "There is no art"),
+            ArtError::InvalidUrl(url) => write!(f, "Invalid art URL: {url}"),
+            ArtError::UnsupportedScheme(scheme) => write!(f, "Unsupported art URL scheme: {scheme}"),
+            ArtError::Io(e) => write!(f, "Failed to read the art: {e}"),
+            ArtError::Http(e) => write!(f, "Failed to fetch the art: {e}"),
+            ArtError::InvalidData => write!(f, "Malformed data URI")
+        }
+    }
+}
+impl std::error::Error for ArtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArtError::Io(e) => Some(e),
+            ArtError::Http(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl Metadata {
+    /// Loads the bytes of the [`art_url`](Self::art_url): local files are read directly, `http(s)` URLs are fetched, and `data:` URIs are decoded inline.
+    /// <br>The bytes are returned as they are (usually a PNG or a JPEG), decoding the image is up to the caller.
+    pub async fn load_art(&self) -> Result<Vec<u8>, ArtError> {
+        let art_url = self.art_url.as_deref().filter(|url| !url.is_empty()).ok_or(ArtError::NoArt)?;
+
+        // Not parsed as a URL, as the data can be huge, and it doesn't need to be
+        if let Some(data) = art_url.strip_prefix("data:") {
+            return decode_data(data);
+        }
+
+        let url = Url::parse(art_url).map_err(|_| ArtError::InvalidUrl(art_url.to_string()))?;
+        match url.scheme() {
+            "file" => {
+                let path = url.to_file_path().map_err(|_| ArtError::InvalidUrl(art_url.to_string()))?;
+                tokio::fs::read(path).await.map_err(ArtError::Io)
+            },
+            "http" | "https" => {
+                let response = reqwest::get(url).await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(ArtError::Http)?;
+
+                Ok(response.bytes().await.map_err(ArtError::Http)?.to_vec())
+            },
+            scheme => Err(ArtError::UnsupportedScheme(scheme.to_string()))
+        }
+    }
+}
+
+/// Decodes the part of a `data:` URI after the scheme, like `image/png;base64,iVBORw0...`
+fn decode_data(data: &str) -> Result<Vec<u8>, ArtError> {
+    let (header, payload) = data.split_once(',').ok_or(ArtError::InvalidData)?;
+
+    if header.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload.trim())
+            .map_err(|_| ArtError::InvalidData)
+    } else {
+        percent_decode(payload)
+    }
+}
+
+/// Decodes the `%XX` escapes of a non-base64 `data:` URI
+fn percent_decode(payload: &str) -> Result<Vec<u8>, ArtError> {
+    let bytes = payload.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = payload.get(i + 1..i + 3).ok_or(ArtError::InvalidData)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| ArtError::InvalidData)?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Ok(decoded)
+}
@@ -0,0 +1,117 @@
+//! The opt-in cache of the properties, see [`Player::enable_property_cache`]
+
+use std::{collections::HashMap, sync::Arc};
+
+use futures::StreamExt as _;
+use tokio::task::AbortHandle;
+use zbus::zvariant::OwnedValue;
+
+use crate::Error;
+
+use super::{Interface, Player, Property, properties, value};
+
+/// The values of the properties, and the task keeping them up to date. Dropping it stops the task.
+#[derive(Debug)]
+pub(super) struct PropertyCache {
+    values: HashMap<(Interface, String), OwnedValue>,
+    task: AbortHandle
+}
+impl Drop for PropertyCache {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Player {
+    /// Starts caching the properties, so [`get`](Self::get) only asks the player the first time, and then reads the cache, which is kept up to date by the changes the player reports.
+    /// <br>Useful for dashboards reading the same properties over and over. The [`Position`](properties::Position) is never cached, as its changes aren't reported.
+    /// <br><br>The cache is shared by the clones of this player, and is kept until [`disable_property_cache`](Self::disable_property_cache) is called, or every clone is dropped. Calling this again does nothing.
+    pub async fn enable_property_cache(&self) -> Result<(), Error> {
+        if self.cache.lock().unwrap().is_some() {
+            return Ok(());
+        }
+
+        // Subscribe before anything is cached, to not miss a change
+        let mut changes = self.properties_proxy.receive_properties_changed().await?;
+        let mut owner_changes = self.properties_proxy.inner().receive_owner_changed().await?;
+
+        let state = Arc::downgrade(&self.cache);
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    change = changes.next() => {
+                        let Some(change) = change else { break };
+                        let Ok(args) = change.args() else { continue };
+                        let Some(interface) = Interface::from_name(args.interface_name().as_str()) else { continue };
+
+                        let Some(state) = state.upgrade() else { break };
+                        if let Some(cache) = state.lock().unwrap().as_mut() {
+                            for (name, value) in args.changed_properties() {
+                                if let Ok(value) = value.try_to_owned() {
+                                    cache.values.insert((interface, name.to_string()), value::unwrap_variant(value));
+                                }
+                            }
+                            // Only the names are sent, the values are read again on the next get
+                            for name in args.invalidated_properties().iter() {
+                                cache.values.remove(&(interface, name.to_string()));
+                            }
+                        }
+                    },
+                    owner = owner_changes.next() => {
+                        if owner.is_none() { break };
+
+                        // A restarted player doesn't report its properties as changed, so none of them can be trusted
+                        let Some(state) = state.upgrade() else { break };
+                        if let Some(cache) = state.lock().unwrap().as_mut() {
+                            cache.values.clear();
+                        }
+                    }
+                }
+            }
+
+            // The connection is gone, so the cache can't be kept up to date anymore
+            if let Some(state) = state.upgrade() {
+                state.lock().unwrap().take();
+            }
+        });
+
+        let mut cache = self.cache.lock().unwrap();
+        match cache.as_ref() {
+            // Another call enabled it in the meantime
+            Some(_) => task.abort(),
+            None => *cache = Some(PropertyCache { values: HashMap::new(), task: task.abort_handle() })
+        }
+
+        Ok(())
+    }
+
+    /// Stops caching the properties, see [`enable_property_cache`](Self::enable_property_cache). Does nothing if it wasn't enabled.
+    pub fn disable_property_cache(&self) {
+        self.cache.lock().unwrap().take();
+    }
+
+    /// The cached value of the property, if the cache is enabled, and the value is in it
+    pub(super) fn cached<P: Property>(&self, property: &P) -> Option<OwnedValue> {
+        if property.name() == properties::Position.name() {
+            return None;
+        }
+
+        self.cache.lock().unwrap()
+            .as_ref()?
+            .values
+            .get(&(property.interface(), property.name().to_string()))?
+            .try_clone()
+            .ok()
+    }
+
+    /// Puts the value read from the player into the cache, if it's enabled. A value the player reported in the meantime is kept, as that's newer.
+    pub(super) fn cache_value<P: Property>(&self, property: &P, value: &OwnedValue) {
+        if property.name() == properties::Position.name() {
+            return;
+        }
+
+        if let Some(cache) = self.cache.lock().unwrap().as_mut() && let Ok(value) = value.try_clone() {
+            cache.values.entry((property.interface(), property.name().to_string())).or_insert(value);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// The identifying information of a [`Player`](super::Player), in a single struct. Created by [`Player::descriptor`](super::Player::descriptor).
+/// <br>Useful for logging, or for exposing the players over some IPC, as with the `serde` feature it's [`Serialize`](zbus::export::serde::Serialize).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlayerDescriptor {
+    /// The well known name, for example `org.mpris.MediaPlayer2.vlc`
+    pub bus_name: String,
+    /// The unique name of the player's connection (like `:1.42`), if it's still on the bus
+    pub unique_name: Option<String>,
+    /// See [`properties::Identity`](super::properties::Identity), empty if the player didn't provide it
+    pub identity: String,
+    /// See [`properties::DesktopEntry`](super::properties::DesktopEntry), empty if the player didn't provide it
+    pub desktop_entry: String,
+    /// See [`properties::CanControl`](super::properties::CanControl)
+    pub can_control: bool,
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
 /// A dbus, MPRIS interface
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Interface {
     #[default]
     MediaPlayer2,
@@ -9,18 +9,15 @@ pub enum Interface {
     TrackList,
     Playlists
 }
-impl ToString for Interface {
-    fn to_string(&self) -> String {
-        use Interface::*;
-        match *self {
-            MediaPlayer2 => String::from("org.mpris.MediaPlayer2"),
-            Player => String::from("org.mpris.MediaPlayer2.Player"),
-            TrackList => String::from("org.mpris.MediaPlayer2.TrackList"),
-            Playlists => String::from("org.mpris.MediaPlayer2.Playlists")
-        }
+impl fmt::Display for Interface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
     }
 }
 impl Interface {
+    /// Every MPRIS interface
+    pub const ALL: [Interface; 4] = [Interface::MediaPlayer2, Interface::Player, Interface::TrackList, Interface::Playlists];
+
     pub fn as_str(&self) -> &'static str {
         use Interface::*;
         match *self {
@@ -30,10 +27,16 @@ impl Interface {
             Playlists => "org.mpris.MediaPlayer2.Playlists",
         }
     }
+
+    /// The interface with the D-Bus name, or `None` if it's not an MPRIS one
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|interface| interface.as_str() == name)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-/// The state of the playback
+/// The state of the playback, the value of the [`PlaybackStatus`](crate::properties::PlaybackStatus) property.
+/// <br>The property is a separate type (like every other [`Property`](crate::properties::Property)), so this is the only type of the status itself.
 pub enum Playback {
     Playing,
     Paused,
@@ -41,32 +44,26 @@ pub enum Playback {
     Stopped
 }
 impl Playback{
-    pub fn to_string(&self) -> String {
+    pub fn as_str(&self) -> &'static str {
         match *self {
             Playback::Paused => "Paused",
             Playback::Playing => "Playing",
             Playback::Stopped => "Stopped"
-        }.to_string()
+        }
     }
 }
 impl From<String> for Playback {
     fn from(value: String) -> Self {
-        let value = value.to_lowercase();
-        if value == String::from("playing") {
-            Self::Playing
-        } else if value == String::from("paused") {
-            Self::Paused
-        } else {
-            Self::Stopped
-        }
+        Self::from(value.as_str())
     }
 }
 impl From<&str> for Playback {
+    /// Case insensitive, and ignores the surrounding whitespace, as not every player sends it exactly as the specs say
     fn from(value: &str) -> Self {
-        let value = value.to_lowercase();
-        if value == String::from("playing") {
+        let value = value.trim().to_lowercase();
+        if value == "playing" {
             Self::Playing
-        } else if value == String::from("paused") {
+        } else if value == "paused" {
             Self::Paused
         } else {
             Self::Stopped
@@ -75,12 +72,13 @@ impl From<&str> for Playback {
 }
 impl fmt::Display for Playback {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self.as_str())
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-/// The state of the loop
+/// The state of the loop, the value of the [`LoopStatus`](crate::properties::LoopStatus) property.
+/// <br>The property is a separate type (like every other [`Property`](crate::properties::Property)), so this is the only type of the status itself.
 pub enum Loop {
     #[default]
     /// The playback will stop after the end of the playlist
@@ -91,32 +89,26 @@ pub enum Loop {
     Playlist
 }
 impl Loop{
-    pub fn to_string(&self) -> String {
+    pub fn as_str(&self) -> &'static str {
         match *self {
             Loop::None => "None",
             Loop::Track => "Track",
             Loop::Playlist => "Playlist"
-        }.to_string()
+        }
     }
 }
 impl From<String> for Loop {
     fn from(value: String) -> Self {
-        let value = value.to_lowercase();
-        if value == String::from("playlist") {
-            Self::Playlist
-        } else if value == String::from("track") {
-            Self::Track
-        } else {
-            Self::None
-        }
+        Self::from(value.as_str())
     }
 }
 impl From<&str> for Loop {
+    /// Case insensitive, and ignores the surrounding whitespace, as not every player sends it exactly as the specs say
     fn from(value: &str) -> Self {
-        let value = value.to_lowercase();
-        if value == String::from("playlist") {
+        let value = value.trim().to_lowercase();
+        if value == "playlist" {
             Self::Playlist
-        } else if value == String::from("track") {
+        } else if value == "track" {
             Self::Track
         } else {
             Self::None
@@ -125,6 +117,98 @@ impl From<&str> for Loop {
 }
 impl fmt::Display for Loop {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self.as_str())
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The order [`Player::get_playlists`](crate::Player::get_playlists) returns the playlists in. The player may not support all of them, see [`properties::Orderings`](crate::properties::Orderings).
+pub enum PlaylistOrdering {
+    /// By the name of the playlist
+    Alphabetical,
+    /// By the date the playlist was created, the oldest first
+    Created,
+    /// By the date the playlist was last modified, the oldest first
+    Modified,
+    /// By the date the playlist was last played, the oldest first
+    Played,
+    /// In an order the user set
+    UserDefined
+}
+impl PlaylistOrdering {
+    /// The name as specified by the [specs](https://specifications.freedesktop.org/mpris/latest/Playlists_Interface.html#Enum:Playlist_Ordering)
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            PlaylistOrdering::Alphabetical => "Alphabetical",
+            PlaylistOrdering::Created => "CreationDate",
+            PlaylistOrdering::Modified => "ModifiedDate",
+            PlaylistOrdering::Played => "LastPlayDate",
+            PlaylistOrdering::UserDefined => "UserDefined"
+        }
+    }
+
+    /// Parses the name, case insensitively, and ignoring the surrounding whitespace. Returns `None` for names not in the specs.
+    pub(crate) fn from_name(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        [Self::Alphabetical, Self::Created, Self::Modified, Self::Played, Self::UserDefined]
+            .into_iter()
+            .find(|ordering| ordering.as_str().eq_ignore_ascii_case(value))
+    }
+}
+impl fmt::Display for PlaylistOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A URI scheme, like the ones in [`properties::SupportedURIs`](crate::properties::SupportedURIs). See [`Player::supported_uri_schemes`](crate::Player::supported_uri_schemes).
+pub enum UriScheme {
+    File,
+    Http,
+    Https,
+    Rtsp,
+    Ftp,
+    Mms,
+    /// A scheme not listed above, in lowercase
+    Other(String)
+}
+impl UriScheme {
+    pub fn as_str(&self) -> &str {
+        match self {
+            UriScheme::File => "file",
+            UriScheme::Http => "http",
+            UriScheme::Https => "https",
+            UriScheme::Rtsp => "rtsp",
+            UriScheme::Ftp => "ftp",
+            UriScheme::Mms => "mms",
+            UriScheme::Other(scheme) => scheme
+        }
+    }
+
+    /// If `uri` has this scheme, like `file:///music/song.mp3` for [`UriScheme::File`]. Useful to check a URI before [`Player::open_uri`](crate::Player::open_uri).
+    pub fn matches(&self, uri: &str) -> bool {
+        uri.split_once(':').is_some_and(|(scheme, _)| scheme.trim().eq_ignore_ascii_case(self.as_str()))
+    }
+}
+impl From<String> for UriScheme {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+impl From<&str> for UriScheme {
+    /// Case insensitive, and ignores the surrounding whitespace and a trailing `://`, as not every player sends just the scheme
+    fn from(value: &str) -> Self {
+        let value = value.trim().trim_end_matches("://").to_lowercase();
+
+        [Self::File, Self::Http, Self::Https, Self::Rtsp, Self::Ftp, Self::Mms]
+            .into_iter()
+            .find(|scheme| scheme.as_str() == value)
+            .unwrap_or(Self::Other(value))
+    }
+}
+impl fmt::Display for UriScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -1,18 +1,35 @@
 use std::{collections::HashMap, time::Duration};
 
-use zbus::zvariant::OwnedValue;
+use zbus::zvariant::{ObjectPath, OwnedValue};
+
+use super::{micros::micros_to_duration, value::{as_f64, as_i64, as_string, as_string_vec}};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// The special trackid meaning there is no track, thus it can't be used to seek
+pub const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+/// The identity of a track, see [`Metadata::trackid`]
+pub type TrackId = String;
 
 /// Metadata of a media
 /// <br>It's construced from the [metadata specs](https://www.freedesktop.org/wiki/Specifications/mpris-spec/metadata/).
 /// <br>Dont assume any of this is actually provided (other than trackid), but basics such as title, artists, and sometimes the album is provided.
-#[derive(Debug, Clone)]
+/// <br>With the `serde` feature, it's [`Serialize`](zbus::export::serde::Serialize) and [`Deserialize`](zbus::export::serde::Deserialize), with the [length](Self::length) as microseconds, like in MPRIS.
+/// The [`raw`](Self::raw) values are kept, but formats like JSON may not keep their exact D-Bus types (an `x` can come back as a `t`), which this crate reads the same.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Metadata {
     // MPRIS specific things
 
     /// A unique identity for this track within the context of an MPRIS object. 
-    /// <br>This is always provided, but not always accurate (for example browsers might provide bullshit).
-    pub trackid: String,
+    /// <br>The specs say this is always provided, but some players omit it (then it's `None`), and it's not always accurate (for example browsers might provide bullshit).
+    /// <br>It should be an object path, but as some players send a plain string, it's kept as one. See [`trackid_path`](Self::trackid_path) for the typed form.
+    pub trackid: Option<TrackId>,
     /// The length of the track
+    /// <br>A length of 0 (or less) is treated as unknown, as that's what some players send for live streams. The original value is still in [`raw`](Self::raw).
+    #[cfg_attr(feature = "serde", serde(with = "super::micros::serde_micros"))]
     pub length: Option<Duration>,
     /// The URI of the location of the track. You should not assume this will exist when a new track is played. 
     /// <br>Local files will start "file://", but it can be an online URL as well (for example Spotify's desktop player provides a URL).
@@ -23,6 +40,7 @@ pub struct Metadata {
     /// The album name
     pub album: String,
     /// A list of artists
+    /// <br>Some players send all the artists joined into a single string, see [`split_artists`](Self::split_artists) for that case.
     pub artists: Vec<String>,
     /// The list of the album's artists
     pub album_artist: Vec<String>,
@@ -63,43 +81,221 @@ pub struct Metadata {
     pub last_used: String,
     /// The number of times the track has been played
     pub use_count: i64,
+
+    /// The untouched dictionary as it was sent by the player, including the nonstandard keys some players add.
+    pub raw: HashMap<String, OwnedValue>,
 }
 impl Metadata {
     pub fn new_from_hashmap(map: HashMap<String, OwnedValue>) -> Self {
         Self {
-            trackid: match map.get("mpris:trackid") {
-                Some(id) => id.to_string(),
-                None => String::new()
-            },
-            length: map.get("mpris:length").map_or(None, |value| value.downcast_ref::<i64>().ok().map(|d| Duration::from_micros(d as u64))),
-            art_url: map.get("mpris:artUrl").map_or(None, |value| Some(value.to_string())),
-
-            album: map.get("xesam:album").map_or(String::new(), |value| value.to_string()),
-            album_artist: map.get("xesam:albumArtists").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-            artists: map.get("xesam:artist").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-            comments: map.get("xesam:comment").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-            lyricists: map.get("xesam:lyricist").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-            composers: map.get("xesam:composer").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-            genres: map.get("xesam:genre").map_or(Vec::new(), |value| Vec::<String>::try_from(value.clone()).map_or(Vec::new(), |v| v)),
-
-            lyrics: map.get("mpris:asText").map_or(String::new(), |value| value.to_string()),
-            url: map.get("mpris:url").map_or(String::new(), |value| value.to_string()),
-            title: map.get("mpris:title").map_or(String::new(), |value| value.to_string()),
-
-            auto_rating: map.get("xesam:autoRating").map_or(0.0, |value| value.downcast_ref::<f64>().unwrap_or(0.0)),
-            user_rating: map.get("xesam:userRating").map_or(0.0, |value| value.downcast_ref::<f64>().unwrap_or(0.0)),
-
-            bpm: map.get("xesam:audioBPM").map_or(0, |value| value.downcast_ref::<i64>().unwrap_or(0)),
-
-            disc_number: map.get("xesam:discNumber").map_or(0, |value| value.downcast_ref::<i64>().unwrap_or(0)),
-            track_number: map.get("xesam:trackNumber").map_or(0, |value| value.downcast_ref::<i64>().unwrap_or(0)),
-            use_count: map.get("xesam:useCount").map_or(0, |value| value.downcast_ref::<i64>().unwrap_or(0)),
-
-            created: map.get("mpris:contentCreated").map_or(String::new(), |value| value.to_string()),
-            first_used: map.get("mpris:firstUsed").map_or(String::new(), |value| value.to_string()),
-            last_used: map.get("mpris:lastUsed").map_or(String::new(), |value| value.to_string())
+            trackid: map.get("mpris:trackid").and_then(as_trackid),
+            length: map.get("mpris:length").and_then(as_i64).filter(|d| *d > 0).map(micros_to_duration),
+            art_url: map.get("mpris:artUrl").and_then(as_string),
+
+            album: map.get("xesam:album").and_then(as_string).unwrap_or_default(),
+            album_artist: map.get("xesam:albumArtist").and_then(as_string_vec).unwrap_or_default(),
+            artists: map.get("xesam:artist").and_then(as_string_vec).unwrap_or_default(),
+            comments: map.get("xesam:comment").and_then(as_string_vec).unwrap_or_default(),
+            lyricists: map.get("xesam:lyricist").and_then(as_string_vec).unwrap_or_default(),
+            composers: map.get("xesam:composer").and_then(as_string_vec).unwrap_or_default(),
+            genres: map.get("xesam:genre").and_then(as_string_vec).unwrap_or_default(),
+
+            lyrics: map.get("xesam:asText").and_then(as_string).unwrap_or_default(),
+            url: map.get("xesam:url").and_then(as_string).unwrap_or_default(),
+            title: map.get("xesam:title").and_then(as_string).unwrap_or_default(),
+
+            auto_rating: map.get("xesam:autoRating").and_then(as_f64).unwrap_or_default(),
+            user_rating: map.get("xesam:userRating").and_then(as_f64).unwrap_or_default(),
+
+            bpm: map.get("xesam:audioBPM").and_then(as_i64).unwrap_or_default(),
+
+            disc_number: map.get("xesam:discNumber").and_then(as_i64).unwrap_or_default(),
+            track_number: map.get("xesam:trackNumber").and_then(as_i64).unwrap_or_default(),
+            use_count: map.get("xesam:useCount").and_then(as_i64).unwrap_or_default(),
+
+            created: map.get("xesam:contentCreated").and_then(as_string).unwrap_or_default(),
+            first_used: map.get("xesam:firstUsed").and_then(as_string).unwrap_or_default(),
+            last_used: map.get("xesam:lastUsed").and_then(as_string).unwrap_or_default(),
+
+            raw: map
+        }
+    }
+
+    /// If there is no track, meaning the metadata is empty, or the [trackid](Self::trackid) is [`NO_TRACK`].
+    /// <br>Players briefly report this while changing tracks, see [`Player::get_metadata_stable`](crate::Player::get_metadata_stable).
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty() || self.trackid.as_deref() == Some(NO_TRACK)
+    }
+
+    /// The [trackid](Self::trackid) as an object path, which is what [`Player::set_position`](crate::Player::set_position) and the tracklist methods need.
+    /// <br>`None` if the player didn't provide one, or sent one that isn't an object path (some send plain strings).
+    pub fn trackid_path(&self) -> Option<ObjectPath<'_>> {
+        self.trackid.as_deref().and_then(|trackid| ObjectPath::try_from(trackid).ok())
+    }
+
+    /// If the metadata describes a live stream (like an internet radio), meaning it has no [length](Self::length), and its [url](Self::url) is a network one (http, https, rtsp, ...).
+    /// <br>Useful to hide the progress bar, or to show a "LIVE" indicator instead.
+    pub fn is_stream(&self) -> bool {
+        let scheme = self.url
+            .split_once("://")
+            .map(|(scheme, _)| scheme.to_lowercase());
+
+        self.length.is_none() && scheme.is_some_and(|scheme| STREAM_SCHEMES.contains(&scheme.as_str()))
+    }
+
+    /// Maps the metadata onto the [Vorbis comment](https://xiph.org/vorbis/doc/v-comment.html) style tag names (`TITLE`, `ARTIST`, `ALBUM`, `TRACKNUMBER`, `DATE`, `GENRE`, ...), as used by most file taggers.
+    /// <br>Fields that weren't provided are left out. Lists are joined with "; ", and `DATE` is the date part of [`created`](Self::created).
+    pub fn to_tag_map(&self) -> HashMap<&'static str, String> {
+        let mut tags = HashMap::new();
+
+        let mut insert = |key: &'static str, value: String| {
+            if !value.is_empty() {
+                tags.insert(key, value);
+            }
+        };
+
+        insert("TITLE", self.title.clone());
+        insert("ARTIST", self.artists.join("; "));
+        insert("ALBUM", self.album.clone());
+        insert("ALBUMARTIST", self.album_artist.join("; "));
+        insert("GENRE", self.genres.join("; "));
+        insert("COMPOSER", self.composers.join("; "));
+        insert("LYRICIST", self.lyricists.join("; "));
+        insert("DATE", self.created.split('T').next().unwrap_or_default().to_string());
+
+        if self.track_number > 0 {
+            insert("TRACKNUMBER", self.track_number.to_string());
         }
+        if self.disc_number > 0 {
+            insert("DISCNUMBER", self.disc_number.to_string());
+        }
+
+        tags
     }
+
+    /// Compares the fields of two metadata, and returns which have changed from `self` to `new`.
+    pub fn diff(&self, new: &Metadata) -> MetadataDiff {
+        let mut changed = Vec::new();
+
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != new.$field {
+                        changed.push(stringify!($field));
+                    }
+                )*
+            };
+        }
+
+        compare!(
+            trackid, length, art_url, album, artists, album_artist, title, lyrics, bpm, auto_rating, user_rating,
+            comments, composers, disc_number, track_number, url, genres, lyricists, created, first_used, last_used, use_count
+        );
+
+        MetadataDiff { changed }
+    }
+
+    /// Splits the [`artists`](Self::artists) on the commonly used separators (`;`, `,`, ` & `, ` / `).
+    /// <br>Some players put every artist into a single string (like "A, B & C"), this turns that into a proper list.
+    /// <br><br>This is not done automatically, as plenty of band names contain these (for example "Crosby, Stills & Nash").
+    pub fn split_artists(&self) -> Vec<String> {
+        self.artists
+            .iter()
+            .flat_map(|artist| artist.split([';', ',']))
+            .flat_map(|artist| artist.split(" & "))
+            .flat_map(|artist| artist.split(" / "))
+            .map(str::trim)
+            .filter(|artist| !artist.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// The [`album_artist`](Self::album_artist), or the [`artists`](Self::artists) if the player didn't provide one (which is common).
+    /// <br>This is what most UIs show as the album's artist.
+    pub fn effective_album_artist(&self) -> &[String] {
+        if self.album_artist.is_empty() {
+            &self.artists
+        } else {
+            &self.album_artist
+        }
+    }
+
+    /// [`created`](Self::created) parsed as a date, or `None` if it's missing or not ISO 8601.
+    /// <br>Dates without a timezone (`2007-04-12T10:00:00`) or without a time (`2007-04-12`) are read as UTC.
+    #[cfg(feature = "chrono")]
+    pub fn created_at(&self) -> Option<DateTime<FixedOffset>> {
+        parse_date(&self.created)
+    }
+
+    /// [`first_used`](Self::first_used) parsed as a date, the same way as [`created_at`](Self::created_at).
+    #[cfg(feature = "chrono")]
+    pub fn first_used_at(&self) -> Option<DateTime<FixedOffset>> {
+        parse_date(&self.first_used)
+    }
+
+    /// [`last_used`](Self::last_used) parsed as a date, the same way as [`created_at`](Self::created_at).
+    #[cfg(feature = "chrono")]
+    pub fn last_used_at(&self) -> Option<DateTime<FixedOffset>> {
+        parse_date(&self.last_used)
+    }
+
+    /// The size of the album art in pixels as (width, height), if the player provided it.
+    /// <br>This is not part of the specs, only a few players send it under nonstandard keys, so expect `None` most of the time.
+    pub fn art_dimensions(&self) -> Option<(u32, u32)> {
+        ART_DIMENSION_KEYS.iter().find_map(|(width, height)| {
+            let width = self.raw.get(*width).and_then(as_i64).and_then(|v| u32::try_from(v).ok())?;
+            let height = self.raw.get(*height).and_then(as_i64).and_then(|v| u32::try_from(v).ok())?;
+
+            Some((width, height))
+        })
+    }
+}
+
+/// The fields that changed between two [`Metadata`], see [`Metadata::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataDiff {
+    /// The names of the changed fields, as they are named in [`Metadata`], for example `"title"` or `"art_url"`
+    pub changed: Vec<&'static str>
+}
+impl MetadataDiff {
+    /// If nothing has changed
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+}
+
+/// Vendor specific (width, height) key pairs of the album art's size
+const ART_DIMENSION_KEYS: [(&str, &str); 2] = [
+    ("mpris:artUrlWidth", "mpris:artUrlHeight"),
+    ("mpris:artWidth", "mpris:artHeight"),
+];
+
+/// URI schemes used for network streams
+const STREAM_SCHEMES: [&str; 6] = ["http", "https", "rtsp", "rtmp", "mms", "icy"];
+
+/// Parses an ISO 8601 date, as the xesam date fields should be.
+/// <br>Besides the full form (`2007-04-12T10:00:00+02:00`), it accepts the ones without a timezone (`2007-04-12T10:00:00`) and without a time (`2007-04-12`), which are read as UTC.
+/// <br>Players also send a space instead of the `T` (`2007-04-12 10:00:00`), and offsets without the colon (`+0200`), so those are accepted too.
+#[cfg(feature = "chrono")]
+fn parse_date(date: &str) -> Option<DateTime<FixedOffset>> {
+    let date = date.trim();
+    let utc = FixedOffset::east_opt(0)?;
+
+    let date = match date.split_at_checked(10) {
+        Some((day, time)) if time.starts_with(' ') => format!("{day}T{}", &time[1..]),
+        _ => date.to_string()
+    };
+    let date = date.as_str();
+
+    DateTime::parse_from_rfc3339(date).ok()
+        .or_else(|| DateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f%z").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f").ok().map(|date| date.and_utc().with_timezone(&utc)))
+        .or_else(|| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok().map(|date| date.and_time(NaiveTime::MIN).and_utc().with_timezone(&utc)))
+}
+
+/// Reads a trackid, which should be an object path, but some players send it as a plain (sometimes empty) string
+fn as_trackid(value: &OwnedValue) -> Option<TrackId> {
+    as_string(value).filter(|trackid| !trackid.is_empty())
 }
 impl From<HashMap<String, OwnedValue>> for Metadata {
     fn from(value: HashMap<String, OwnedValue>) -> Self {
@@ -0,0 +1,45 @@
+//! Conversions between [`Duration`] and the microseconds MPRIS uses for positions, offsets and lengths
+
+use std::time::Duration;
+
+/// Converts a duration to microseconds, saturating at [`i64::MAX`] for absurdly long durations
+pub(crate) fn duration_to_micros(duration: Duration) -> i64 {
+    i64::try_from(duration.as_micros()).unwrap_or(i64::MAX)
+}
+
+/// Converts microseconds to a duration. Negative values (which some players send) are clamped to zero.
+pub(crate) fn micros_to_duration(micros: i64) -> Duration {
+    Duration::from_micros(micros.max(0) as u64)
+}
+
+/// The longest position MPRIS can represent, [`i64::MAX`] microseconds
+pub(crate) const MAX_POSITION: Duration = Duration::from_micros(i64::MAX as u64);
+
+/// Estimates the position after `elapsed` time of playing at `rate`, clamped to [`MAX_POSITION`].
+/// <br>A rate that is NaN, infinite or negative (which misbehaving players can report) is treated as 1.0.
+pub(crate) fn advance(position: Duration, elapsed: Duration, rate: f64) -> Duration {
+    let rate = if rate.is_finite() && rate >= 0.0 { rate } else { 1.0 };
+    let micros = position.as_micros() as f64 + elapsed.as_micros() as f64 * rate;
+
+    // The limit isn't exact as an f64, so it's applied again after the conversion
+    Duration::from_micros(micros.min(MAX_POSITION.as_micros() as f64) as u64).min(MAX_POSITION)
+}
+
+/// (De)serializes an optional duration as microseconds, for [`Metadata::length`](super::Metadata::length)
+#[cfg(feature = "serde")]
+pub(crate) mod serde_micros {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&super::duration_to_micros(*duration)),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<i64>::deserialize(deserializer)?.map(super::micros_to_duration))
+    }
+}
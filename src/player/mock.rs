@@ -0,0 +1,520 @@
+//! An in-process MPRIS player for the tests, connected to a [`Player`] through a peer-to-peer connection
+
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use zbus::{Connection, connection, interface, names::OwnedBusName, object_server::SignalEmitter, zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value}};
+
+use super::Player;
+
+/// Records the method calls it receives, and serves whatever properties it was set up with
+#[derive(Debug, Clone)]
+pub(crate) struct MockPlayer {
+    pub calls: Arc<Mutex<Vec<String>>>,
+    pub playback_status: String,
+    pub position: i64,
+    /// Fails reading the position, like players that support play/pause, but not the position
+    pub position_unsupported: bool,
+    pub rate: f64,
+    pub volume: f64,
+    pub can_control: bool,
+    pub can_play: bool,
+    pub can_pause: bool,
+    pub can_go_next: bool,
+    pub can_go_previous: bool,
+    pub can_seek: bool,
+    pub metadata: HashMap<String, OwnedValue>,
+    /// Serves the TrackList interface with these tracks (each one's metadata having a trackid), if set
+    pub tracks: Option<Vec<HashMap<String, OwnedValue>>>,
+    pub can_edit_tracks: bool,
+    /// Serves the Playlists interface with these playlists (id, name), if set
+    pub playlists: Option<Vec<(String, String)>>,
+    pub active_playlist: Option<String>,
+    /// Sends the metadata as an array instead of a dict, like some broken players do
+    pub malformed_metadata: bool,
+    pub loop_status: String,
+    pub fullscreen: bool,
+    pub can_set_fullscreen: bool,
+    pub supported_uri_schemes: Vec<String>,
+    /// Accepts writes to the properties, but doesn't apply them, like some players do
+    pub ignore_writes: bool,
+    /// Closes the connection instead of replying to Quit, like some players do
+    pub drop_on_quit: bool,
+    /// Never replies to Quit, but stays on the bus, like a hung player
+    pub hang_on_quit: bool,
+    /// Fails Quit with `NoReply`, but stays on the bus, like the bus does when a hung player times out
+    pub no_reply_on_quit: bool,
+    /// Sends the Position as a `t` and the Rate as an `i`, instead of a `x` and a `d`, like some players do
+    pub loose_numbers: bool,
+}
+impl Default for MockPlayer {
+    fn default() -> Self {
+        Self {
+            calls: Arc::new(Mutex::new(Vec::new())),
+            playback_status: "Stopped".to_string(),
+            position: 0,
+            position_unsupported: false,
+            rate: 1.0,
+            volume: 1.0,
+            can_control: true,
+            can_play: true,
+            can_pause: true,
+            can_go_next: true,
+            can_go_previous: true,
+            can_seek: true,
+            metadata: HashMap::new(),
+            tracks: None,
+            can_edit_tracks: true,
+            playlists: None,
+            active_playlist: None,
+            malformed_metadata: false,
+            loop_status: "None".to_string(),
+            fullscreen: false,
+            can_set_fullscreen: false,
+            supported_uri_schemes: Vec::new(),
+            ignore_writes: false,
+            drop_on_quit: false,
+            hang_on_quit: false,
+            no_reply_on_quit: false,
+            loose_numbers: false,
+        }
+    }
+}
+impl MockPlayer {
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+/// The root interface of [`MockPlayer`]
+struct MockRoot(MockPlayer);
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl MockRoot {
+    async fn quit(&self, #[zbus(connection)] connection: &Connection) -> zbus::fdo::Result<()> {
+        self.0.record(String::from("Quit"));
+
+        if self.0.drop_on_quit {
+            let _ = connection.clone().close().await;
+        }
+        if self.0.hang_on_quit {
+            std::future::pending::<()>().await;
+        }
+        if self.0.no_reply_on_quit {
+            return Err(zbus::fdo::Error::NoReply(String::from("Did not receive a reply")));
+        }
+
+        Ok(())
+    }
+
+    /// Always true, but doubly wrapped into a variant, like some players do
+    #[zbus(property)]
+    fn can_quit(&self) -> OwnedValue {
+        OwnedValue::try_from(Value::Value(Box::new(Value::Bool(true)))).unwrap()
+    }
+
+    #[zbus(property)]
+    fn fullscreen(&self) -> bool {
+        self.0.fullscreen
+    }
+
+    #[zbus(property)]
+    fn set_fullscreen(&mut self, value: bool) {
+        self.0.record(format!("Fullscreen = {value}"));
+
+        if !self.0.ignore_writes {
+            self.0.fullscreen = value;
+        }
+    }
+
+    #[zbus(property)]
+    fn can_set_fullscreen(&self) -> bool {
+        self.0.can_set_fullscreen
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        self.0.supported_uri_schemes.clone()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl MockPlayer {
+    async fn open_uri(&mut self, uri: String, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>) {
+        self.record(format!("OpenUri({uri})"));
+
+        self.metadata = metadata_with_trackid("/track/opened");
+        _ = self.metadata_changed(&emitter).await;
+    }
+
+    fn play(&mut self) {
+        self.record(String::from("Play"));
+        self.playback_status = String::from("Playing");
+    }
+
+    fn pause(&mut self) {
+        self.record(String::from("Pause"));
+        self.playback_status = String::from("Paused");
+    }
+
+    fn next(&self) {
+        self.record(String::from("Next"));
+    }
+
+    fn previous(&self) {
+        self.record(String::from("Previous"));
+    }
+
+    fn play_pause(&mut self) {
+        self.record(String::from("PlayPause"));
+        self.playback_status = match self.playback_status.as_str() {
+            "Playing" => String::from("Paused"),
+            _ => String::from("Playing")
+        };
+    }
+
+    fn stop(&mut self) {
+        self.record(String::from("Stop"));
+        self.playback_status = String::from("Stopped");
+    }
+
+    fn seek(&self, offset: i64) {
+        self.record(format!("Seek({offset})"));
+    }
+
+    /// Jumps to the position, and reports it with `Seeked`, unless it [ignores writes](Self::ignore_writes)
+    async fn set_position(&mut self, track_id: ObjectPath<'_>, position: i64, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>) {
+        self.record(format!("SetPosition({track_id}, {position})"));
+
+        if !self.ignore_writes {
+            self.position = position;
+            _ = Self::seeked(&emitter, position).await;
+        }
+    }
+
+    #[zbus(signal)]
+    pub async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> String {
+        self.playback_status.clone()
+    }
+
+    #[zbus(property)]
+    fn position(&self) -> zbus::fdo::Result<OwnedValue> {
+        match (self.position_unsupported, self.loose_numbers) {
+            (true, _) => Err(zbus::fdo::Error::NotSupported(String::from("Position is not supported"))),
+            (false, true) => Ok(OwnedValue::from(self.position as u64)),
+            (false, false) => Ok(OwnedValue::from(self.position))
+        }
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> OwnedValue {
+        match self.loose_numbers {
+            true => OwnedValue::from(self.rate as i32),
+            false => OwnedValue::from(self.rate)
+        }
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.volume
+    }
+
+    #[zbus(property)]
+    fn set_volume(&mut self, value: f64) {
+        self.record(format!("Volume = {value}"));
+
+        if !self.ignore_writes {
+            self.volume = value;
+        }
+    }
+
+    #[zbus(property)]
+    fn can_control(&self) -> bool {
+        self.can_control
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        self.can_play
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        self.can_pause
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        self.can_go_next
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        self.can_go_previous
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.can_seek
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> OwnedValue {
+        match self.malformed_metadata {
+            true => OwnedValue::try_from(Value::from(vec!["mpris:trackid", "/track/1"])).unwrap(),
+            false => OwnedValue::try_from(Value::from(self.metadata.clone())).unwrap()
+        }
+    }
+
+    #[zbus(property)]
+    fn loop_status(&self) -> String {
+        self.loop_status.clone()
+    }
+
+    #[zbus(property)]
+    fn set_loop_status(&mut self, value: String) {
+        self.record(format!("LoopStatus = {value}"));
+
+        if !self.ignore_writes {
+            self.loop_status = value;
+        }
+    }
+}
+
+/// The TrackList interface of [`MockPlayer`], only served if it has [tracks](MockPlayer::tracks)
+pub(crate) struct MockTrackList(MockPlayer);
+impl MockTrackList {
+    fn track_metadata(&self) -> &[HashMap<String, OwnedValue>] {
+        self.0.tracks.as_deref().unwrap_or_default()
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.TrackList")]
+impl MockTrackList {
+    fn get_tracks_metadata(&self, track_ids: Vec<ObjectPath<'_>>) -> Vec<HashMap<String, OwnedValue>> {
+        track_ids
+            .iter()
+            .filter_map(|track_id| self.track_metadata().iter().find(|track| trackid(track).as_deref() == Some(track_id.as_str())))
+            .cloned()
+            .collect()
+    }
+
+    /// Reports the track as added with the trackid `/track/added`, but doesn't really add it
+    async fn add_track(&self, uri: String, after_track: ObjectPath<'_>, set_as_current: bool, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>) {
+        self.0.record(format!("AddTrack({uri}, {after_track}, {set_as_current})"));
+        _ = Self::track_added(&emitter, metadata_with_trackid("/track/added"), after_track).await;
+    }
+
+    /// Reports the track as removed, but doesn't really remove it
+    async fn remove_track(&self, track_id: ObjectPath<'_>, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>) {
+        self.0.record(format!("RemoveTrack({track_id})"));
+        _ = Self::track_removed(&emitter, track_id).await;
+    }
+
+    fn go_to(&self, track_id: ObjectPath<'_>) {
+        self.0.record(format!("GoTo({track_id})"));
+    }
+
+    #[zbus(property)]
+    fn tracks(&self) -> Vec<OwnedObjectPath> {
+        self.track_metadata()
+            .iter()
+            .filter_map(trackid)
+            .map(|track_id| OwnedObjectPath::try_from(track_id).unwrap())
+            .collect()
+    }
+
+    #[zbus(property)]
+    fn can_edit_tracks(&self) -> bool {
+        self.0.can_edit_tracks
+    }
+
+    #[zbus(signal)]
+    pub async fn track_list_replaced(emitter: &SignalEmitter<'_>, tracks: Vec<ObjectPath<'_>>, current_track: ObjectPath<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn track_added(emitter: &SignalEmitter<'_>, metadata: HashMap<String, OwnedValue>, after_track: ObjectPath<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn track_removed(emitter: &SignalEmitter<'_>, track_id: ObjectPath<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    pub async fn track_metadata_changed(emitter: &SignalEmitter<'_>, track_id: ObjectPath<'_>, metadata: HashMap<String, OwnedValue>) -> zbus::Result<()>;
+}
+
+/// The Playlists interface of [`MockPlayer`], only served if it has [playlists](MockPlayer::playlists)
+pub(crate) struct MockPlaylists(MockPlayer);
+impl MockPlaylists {
+    fn playlist(&self, (id, name): &(String, String)) -> (OwnedObjectPath, String, String) {
+        (OwnedObjectPath::try_from(id.as_str()).unwrap(), name.clone(), String::new())
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Playlists")]
+impl MockPlaylists {
+    fn activate_playlist(&mut self, playlist_id: ObjectPath<'_>) {
+        self.0.record(format!("ActivatePlaylist({playlist_id})"));
+        self.0.active_playlist = Some(playlist_id.to_string());
+    }
+
+    /// Only knows the alphabetical order
+    fn get_playlists(&self, index: u32, max_count: u32, order: String, reverse_order: bool) -> Vec<(OwnedObjectPath, String, String)> {
+        self.0.record(format!("GetPlaylists({index}, {max_count}, {order}, {reverse_order})"));
+
+        let mut playlists = self.0.playlists.clone().unwrap_or_default();
+        playlists.sort_by(|(_, a), (_, b)| a.cmp(b));
+        if reverse_order {
+            playlists.reverse();
+        }
+
+        playlists.iter().skip(index as usize).take(max_count as usize).map(|playlist| self.playlist(playlist)).collect()
+    }
+
+    #[zbus(property)]
+    fn playlist_count(&self) -> u32 {
+        self.0.playlists.as_ref().map_or(0, |playlists| playlists.len() as u32)
+    }
+
+    #[zbus(property)]
+    fn orderings(&self) -> Vec<String> {
+        vec![String::from("Alphabetical"), String::from("SomethingElse")]
+    }
+
+    #[zbus(property)]
+    fn active_playlist(&self) -> (bool, (OwnedObjectPath, String, String)) {
+        let active = self.0.active_playlist.as_ref()
+            .and_then(|id| self.0.playlists.as_ref()?.iter().find(|(playlist, _)| playlist == id));
+
+        match active {
+            Some(playlist) => (true, self.playlist(playlist)),
+            None => (false, (OwnedObjectPath::try_from("/").unwrap(), String::new(), String::new()))
+        }
+    }
+
+    #[zbus(signal)]
+    pub async fn playlist_changed(emitter: &SignalEmitter<'_>, playlist: (ObjectPath<'_>, &str, &str)) -> zbus::Result<()>;
+}
+
+/// The trackid in the metadata of a track
+fn trackid(track: &HashMap<String, OwnedValue>) -> Option<String> {
+    track.get("mpris:trackid")?.downcast_ref::<ObjectPath>().ok().map(|path| path.to_string())
+}
+
+/// Serves `mock` and returns a [`Player`] connected to it. The server connection must be kept alive.
+pub(crate) async fn connect(mock: MockPlayer) -> (Player, Connection) {
+    let (client, server) = tokio::net::UnixStream::pair().unwrap();
+    let guid = zbus::Guid::generate();
+
+    let (client, server) = tokio::join!(
+        connection::Builder::unix_stream(client).p2p().build(),
+        async {
+            let builder = connection::Builder::unix_stream(server)
+                .server(guid).unwrap()
+                .p2p();
+
+            serve(builder, mock).build().await
+        }
+    );
+
+    let name = OwnedBusName::try_from("org.mpris.MediaPlayer2.mock").unwrap();
+    (Player::new(name, client.unwrap()).await.unwrap(), server.unwrap())
+}
+
+/// Serves every interface of `mock` on the connection being built
+fn serve(builder: connection::Builder<'_>, mock: MockPlayer) -> connection::Builder<'_> {
+    let builder = match mock.tracks {
+        Some(_) => builder.serve_at("/org/mpris/MediaPlayer2", MockTrackList(mock.clone())).unwrap(),
+        None => builder
+    };
+    let builder = match mock.playlists {
+        Some(_) => builder.serve_at("/org/mpris/MediaPlayer2", MockPlaylists(mock.clone())).unwrap(),
+        None => builder
+    };
+
+    builder
+        .serve_at("/org/mpris/MediaPlayer2", MockRoot(mock.clone())).unwrap()
+        .serve_at("/org/mpris/MediaPlayer2", mock).unwrap()
+}
+
+/// A private `dbus-daemon`, killed on drop
+pub(crate) struct PrivateBus {
+    process: std::process::Child,
+    pub address: String,
+}
+impl PrivateBus {
+    /// Starts a private session bus.
+    /// <br>Panics if `dbus-daemon` can't be started, so a missing daemon fails the bus tests instead of silently skipping them.
+    pub fn start() -> Self {
+        use std::{io::{BufRead, BufReader}, process::{Command, Stdio}};
+
+        let mut process = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("The bus tests need dbus-daemon in the PATH");
+
+        let mut address = String::new();
+        BufReader::new(process.stdout.take().unwrap()).read_line(&mut address).expect("Failed to read the address of dbus-daemon");
+
+        Self { process, address: address.trim().to_string() }
+    }
+
+    /// Serves `mock` on the bus, and returns a [`Player`] connected to it through a separate connection.
+    /// <br>Both the returned server connection and the bus must be kept alive.
+    pub async fn connect(&self, mock: MockPlayer) -> (Player, Connection) {
+        self.connect_as("org.mpris.MediaPlayer2.mock", mock).await
+    }
+
+    /// Like [`connect`](Self::connect), but serves the player under `name`, so several can be on the bus at once
+    pub async fn connect_as(&self, name: &str, mock: MockPlayer) -> (Player, Connection) {
+        let builder = connection::Builder::address(self.address.as_str()).unwrap()
+            .name(name).unwrap();
+        let server = serve(builder, mock).build().await.unwrap();
+
+        let client = self.client().await;
+        let name = OwnedBusName::try_from(name).unwrap();
+
+        (Player::new(name, client).await.unwrap(), server)
+    }
+
+    /// A new client connection to the bus
+    pub async fn client(&self) -> Connection {
+        connection::Builder::address(self.address.as_str()).unwrap().build().await.unwrap()
+    }
+
+    /// An [`Mpris`](crate::Mpris) on the bus, through a separate connection
+    pub async fn mpris(&self) -> crate::Mpris<'static> {
+        crate::Mpris::new_from_connection(self.client().await).await.unwrap()
+    }
+
+    /// The number of match rules the bus holds for `connection`
+    pub async fn match_rules(&self, connection: &Connection) -> u32 {
+        let stats = zbus::Proxy::new(connection, "org.freedesktop.DBus", "/org/freedesktop/DBus", "org.freedesktop.DBus.Debug.Stats").await.unwrap();
+        let unique_name = connection.unique_name().unwrap().to_string();
+        let reply: HashMap<String, OwnedValue> = stats.call("GetConnectionStats", &(unique_name,)).await.unwrap();
+
+        reply.get("MatchRules").unwrap().downcast_ref::<u32>().unwrap()
+    }
+}
+impl Drop for PrivateBus {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Shorthand to build a metadata map with a trackid
+pub(crate) fn metadata_with_trackid(trackid: &str) -> HashMap<String, OwnedValue> {
+    let mut map = HashMap::new();
+    map.insert("mpris:trackid".to_string(), OwnedValue::try_from(Value::from(ObjectPath::try_from(trackid).unwrap())).unwrap());
+    map
+}
+
+/// Emits `Seeked` from the [`MockPlayer`] served on `server`
+pub(crate) async fn emit_seeked(server: &Connection, position: i64) {
+    let iface = server.object_server().interface::<_, MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+    MockPlayer::seeked(iface.signal_emitter(), position).await.unwrap();
+}
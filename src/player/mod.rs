@@ -1,12 +1,25 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::{HashMap, HashSet}, sync::{Arc, Mutex}, time::Duration};
 
-use zbus::{Connection, Proxy, fdo, names::OwnedBusName, proxy, zvariant::{OwnedValue, Value}};
+use futures::{Stream, StreamExt as _};
+use zbus::{Connection, Proxy, fdo, names::{OwnedBusName, OwnedUniqueName}, proxy, zvariant::{OwnedValue, Value}};
 
 mod metadata;
-pub use metadata::Metadata;
+pub use metadata::{Metadata, MetadataDiff, NO_TRACK, TrackId};
+
+mod descriptor;
+pub use descriptor::PlayerDescriptor;
+
+mod player_properties;
+pub use player_properties::PlayerProperties;
+
+mod state;
+pub use state::PlayerState;
+
+mod action;
+pub use action::PlayerAction;
 
 pub use crate::player::properties::{WritableProperty, Property, ControlWritableProperty};
-use crate::{player::{signals::Signal, streams::ParsedPropertyStream}, properties::{PlaybackStatus, Position, Rate}, signals::Seeked, streams::{ParsedSignalStream, PositionStream}};
+use crate::{Error, player::{signals::Signal, streams::ParsedPropertyStream}, properties::{PlaybackStatus, Position, Rate}, signals::Seeked, streams::{BoundedPositionStream, MetadataDiffStream, NowPlayingStream, ParsedSignalStream, PlaybackEventStream, PlayerUpdateStream, PollingPositionStream, PositionStream, SharedStream, StreamYield}};
 
 pub mod properties;
 pub mod signals;
@@ -14,10 +27,43 @@ pub mod signals;
 mod enums;
 pub use enums::*;
 
+mod micros;
+use micros::duration_to_micros;
+
+mod value;
+
+mod tracklist;
+
+mod cache;
+
+mod playlists;
+pub use playlists::Playlist;
+
+#[cfg(feature = "art")]
+mod art;
+#[cfg(feature = "art")]
+pub use art::ArtError;
+
 pub mod streams;
 
+#[cfg(test)]
+pub(crate) mod mock;
+
+/// How many items a [`SharedStream`] buffers for each of its clones
+const SHARED_STREAM_CAPACITY: usize = 16;
+
+/// How long [`Player::apply`] waits for the player to load the track after [`PlayerAction::OpenUri`]
+const LOAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How far off the position may land for [`Player::set_position_verified`] to still count it as a success
+const SEEK_TOLERANCE: Duration = Duration::from_millis(500);
+
+/// How often [`Player::subscribe_volume_smoothed`] yields while ramping, about 60 times a second
+const SMOOTHING_INTERVAL: Duration = Duration::from_millis(16);
 
 /// A player that plays something, or not, who knowns...
+/// <br>The proxies are created once in [`new`](Self::new), and are shared by every call, so an `Arc<Player>` can be used from as many tasks at once as needed.
+/// <br>Reads never modify the player, so dropping their futures midway (like to cancel a slow read when switching players) is safe, even for the ones doing several reads, like [`properties`](Self::properties).
 #[derive(Debug, Clone)]
 pub struct Player {
     /// Well known name
@@ -31,7 +77,13 @@ pub struct Player {
     /// A proxy to "org.mpris.MediaPlayer2.TrackList"
     tracklist_proxy: Option<Proxy<'static>>,
     /// A proxy to "org.mpris.MediaPlayer2.Playlists"
-    playlists_proxy: Option<Proxy<'static>>
+    playlists_proxy: Option<Proxy<'static>>,
+    /// A proxy to "org.freedesktop.DBus.Properties", for [`get_all`](Self::get_all)
+    properties_proxy: fdo::PropertiesProxy<'static>,
+    /// A proxy to "org.freedesktop.DBus.Introspectable", for [`introspect`](Self::introspect)
+    introspectable_proxy: fdo::IntrospectableProxy<'static>,
+    /// Only set while [`enable_property_cache`](Self::enable_property_cache) is in effect
+    cache: Arc<Mutex<Option<cache::PropertyCache>>>
 }
 impl PartialEq for Player {
     // Two players are the same, if their dbus unique names are the same
@@ -40,24 +92,46 @@ impl PartialEq for Player {
     }
 }
 impl Player {
-    async fn create_proxy(connection: &Connection, name: &OwnedBusName, iface: Interface) -> Result<Proxy<'static>, zbus::Error> {
-        Ok(
-            proxy::Builder::new(connection)
-                .destination(name.to_owned())?
-                .path("/org/mpris/MediaPlayer2")?
-                .interface(iface.to_string())?
-                .cache_properties(proxy::CacheProperties::Yes)
-                .build()
-                .await?
-        )
+    async fn create_proxy(connection: &Connection, name: &OwnedBusName, iface: Interface, cache: proxy::CacheProperties) -> Result<Proxy<'static>, zbus::Error> {
+        proxy::Builder::new(connection)
+            .destination(name.to_owned())?
+            .path("/org/mpris/MediaPlayer2")?
+            .interface(iface.to_string())?
+            .cache_properties(cache)
+            .build()
+            .await
     }
 
     /// Creates an instance from a "well known name", and a connection
-    pub async fn new(name: OwnedBusName, connection: Connection) -> Result<Self, zbus::Error> {
-        let proxy = Self::create_proxy(&connection, &name, Interface::MediaPlayer2).await.ok();
-        let player_proxy= Self::create_proxy(&connection, &name, Interface::Player).await.ok();
-        let tracklist_proxy = Self::create_proxy(&connection, &name, Interface::TrackList).await.ok();
-        let playlists_proxy = Self::create_proxy(&connection, &name, Interface::Playlists).await.ok();
+    pub async fn new(name: OwnedBusName, connection: Connection) -> Result<Self, Error> {
+        Self::new_with_cache(name, connection, proxy::CacheProperties::Yes).await
+    }
+
+    /// Like [`new`](Self::new), but doesn't wait for the properties of the player to be cached, which saves a round-trip per interface.
+    /// <br>The cache is filled in the background. It's only used to seed the property streams, [`get`](Self::get) always reads from the player.
+    pub async fn new_lazy(name: OwnedBusName, connection: Connection) -> Result<Self, Error> {
+        Self::new_with_cache(name, connection, proxy::CacheProperties::Lazily).await
+    }
+
+    async fn new_with_cache(name: OwnedBusName, connection: Connection, cache: proxy::CacheProperties) -> Result<Self, Error> {
+        let proxy = Self::create_proxy(&connection, &name, Interface::MediaPlayer2, cache).await.ok();
+        let player_proxy= Self::create_proxy(&connection, &name, Interface::Player, cache).await.ok();
+        let tracklist_proxy = Self::create_proxy(&connection, &name, Interface::TrackList, cache).await.ok();
+        let playlists_proxy = Self::create_proxy(&connection, &name, Interface::Playlists, cache).await.ok();
+
+        // These have no properties to cache, so building them doesn't call the player
+        let properties_proxy = fdo::PropertiesProxy::builder(&connection)
+            .destination(name.to_owned())?
+            .path("/org/mpris/MediaPlayer2")?
+            .cache_properties(proxy::CacheProperties::No)
+            .build()
+            .await?;
+        let introspectable_proxy = fdo::IntrospectableProxy::builder(&connection)
+            .destination(name.to_owned())?
+            .path("/org/mpris/MediaPlayer2")?
+            .cache_properties(proxy::CacheProperties::No)
+            .build()
+            .await?;
 
         Ok(
             Self {
@@ -66,7 +140,10 @@ impl Player {
                 proxy,
                 player_proxy,
                 tracklist_proxy,
-                playlists_proxy
+                playlists_proxy,
+                properties_proxy,
+                introspectable_proxy,
+                cache: Arc::default()
             }
         )
     }
@@ -77,6 +154,128 @@ impl Player {
         self.name.clone()
     }
 
+    /// Returns the part of the [`dbus_name`](Self::dbus_name) after `org.mpris.MediaPlayer2.`, like `vlc`, or `firefox.instance_1_42`.
+    pub fn short_name(&self) -> String {
+        let name = self.name.as_str();
+
+        name.strip_prefix(crate::mpris::MPRIS_PREFIX)
+            .and_then(|name| name.strip_prefix('.'))
+            .unwrap_or(name)
+            .to_string()
+    }
+
+    /// Returns a name of the player to show to users: the [`Identity`](properties::Identity), 
+    /// or if it's empty (or missing), the [`DesktopEntry`](properties::DesktopEntry), and if that's empty too, the [`short_name`](Self::short_name).
+    pub async fn display_name(&self) -> String {
+        let identity = self.get_or(properties::Identity, String::new()).await;
+        if !identity.is_empty() {
+            return identity;
+        }
+
+        let desktop_entry = self.get_or(properties::DesktopEntry, String::new()).await;
+        if !desktop_entry.is_empty() {
+            return desktop_entry;
+        }
+
+        self.short_name()
+    }
+
+    /// Returns the unique name of the connection currently owning the player's name (like `:1.42`).
+    /// <br>Unlike [`dbus_name`](Self::dbus_name), this is different every time the player restarts.
+    pub async fn unique_name(&self) -> Result<OwnedUniqueName, Error> {
+        let dbus = fdo::DBusProxy::new(&self.connection).await?;
+
+        Ok(dbus.get_name_owner(self.name.as_ref()).await?)
+    }
+
+    /// Collects the identifying information of the player into a [`PlayerDescriptor`].
+    /// <br>Properties that can't be read are left empty (or false), rather than failing.
+    pub async fn descriptor(&self) -> PlayerDescriptor {
+        PlayerDescriptor {
+            bus_name: self.name.to_string(),
+            unique_name: self.unique_name().await.ok().map(|name| name.to_string()),
+            identity: self.get_or(properties::Identity, String::new()).await,
+            desktop_entry: self.get_or(properties::DesktopEntry, String::new()).await,
+            can_control: self.get_or(properties::CanControl, false).await,
+        }
+    }
+
+    /// Captures the [`Volume`](properties::Volume), [`PlaybackStatus`] and [`Rate`] of the player, to [restore](Self::restore_state) them later.
+    /// <br>Useful for "duck and restore", like lowering the music for a notification.
+    pub async fn capture_state(&self) -> PlayerState {
+        PlayerState {
+            volume: self.get(properties::Volume).await.ok(),
+            playback: self.get(PlaybackStatus).await.ok(),
+            rate: self.get(Rate).await.ok(),
+        }
+    }
+
+    /// Puts back a [`PlayerState`] captured by [`capture_state`](Self::capture_state). Only what changed since is written.
+    /// <br>The volume and the rate are only restored if [`properties::CanControl`] is true, and the playback only if the player can do it
+    /// (see [`properties::CanPlay`] and [`properties::CanPause`]), so a player that can't be controlled isn't an error.
+    pub async fn restore_state(&self, state: PlayerState) -> Result<(), Error> {
+        let current = self.capture_state().await;
+
+        if self.get_or(properties::CanControl, false).await {
+            if let Some(volume) = state.volume.filter(|volume| current.volume != Some(*volume)) {
+                self.set_controlled(properties::Volume, volume).await?;
+            }
+            if let Some(rate) = state.rate.filter(|rate| current.rate != Some(*rate)) {
+                self.set_controlled(Rate, rate).await?;
+            }
+        }
+
+        match state.playback.filter(|playback| current.playback != Some(*playback)) {
+            Some(Playback::Playing) if self.get_or(properties::CanPlay, false).await => self.play().await,
+            Some(Playback::Paused) if self.get_or(properties::CanPause, false).await => self.pause().await,
+            Some(Playback::Stopped) if self.get_or(properties::CanControl, false).await => self.stop().await,
+            _ => Ok(())
+        }
+    }
+
+    /// Returns the [introspection XML](https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format) of the player's MPRIS object.
+    /// <br>Useful for debugging, as it lists exactly which interfaces, methods, properties and signals the player exposes.
+    pub async fn introspect(&self) -> Result<String, Error> {
+        Ok(self.introspectable_proxy.introspect().await?)
+    }
+
+    /// Whether the player's introspection data (see [`introspect`](Self::introspect)) declares the property writable.
+    /// <br>Players may still refuse a write even if this is true, for example [`ControlWritableProperty`]s are only writable if [`properties::CanControl`] is true.
+    /// <br>Returns false if the introspection data can't be read, or doesn't have the property at all.
+    pub async fn is_writable<P: Property>(&self, property: P) -> bool {
+        let Ok(xml) = self.introspect().await else { return false };
+        let Ok(node) = zbus_xml::Node::try_from(xml.as_str()) else { return false };
+
+        node.interfaces()
+            .iter()
+            .filter(|iface| iface.name().as_str() == property.interface().as_str())
+            .flat_map(|iface| iface.properties())
+            .any(|prop| prop.name().as_str() == property.name() && prop.access().write())
+    }
+
+    /// Returns which of the MPRIS [interfaces](Interface) the player implements, according to its introspection data (see [`introspect`](Self::introspect)).
+    /// <br>More reliable than [`properties::HasTrackList`], which some players set incorrectly. Useful to only show, say, a playlist switcher if the player has [`Interface::Playlists`].
+    pub async fn interfaces(&self) -> Result<HashSet<Interface>, Error> {
+        let xml = self.introspect().await?;
+        let node = zbus_xml::Node::try_from(xml.as_str())
+            .map_err(|e| Error::InvalidIntrospection(e.to_string()))?;
+
+        Ok(
+            Interface::ALL
+                .into_iter()
+                .filter(|interface| node.interfaces().iter().any(|iface| iface.name().as_str() == interface.as_str()))
+                .collect()
+        )
+    }
+
+    /// Returns the process ID of the player, as reported by the bus.
+    /// <br>Useful to kill a player that can't [`quit`](Self::quit) (see [`properties::CanQuit`]), which this crate intentionally doesn't do on its own.
+    pub async fn owner_pid(&self) -> Result<u32, Error> {
+        let dbus = fdo::DBusProxy::new(&self.connection).await?;
+
+        Ok(dbus.get_connection_unix_process_id(self.name.as_ref()).await?)
+    }
+
     fn proxy(&self, interface: Interface) -> Result<&Proxy<'static>, zbus::Error> {
         let iface = match interface {
             Interface::MediaPlayer2 => &self.proxy,
@@ -86,31 +285,102 @@ impl Player {
         };
 
         match iface {
-            Some(v) => Ok(&v),
+            Some(v) => Ok(v),
             None => Err(zbus::Error::InterfaceNotFound)
         }
     }
 
     /// Parses a property from the player. See [`properties`] for more
-    pub async fn get<P>(&self, property: P) -> Result<P::Output, zbus::Error>
+    /// <br>Fails with [`Error::UnsupportedProperty`] if the player doesn't have it, and with [`Error::ParseFailed`] if it sent something else than what the specs say.
+    /// <br>This always asks the player, rather than reading the cache of the proxies (which only the streams use), 
+    /// as the cache is stale for the properties the player doesn't report the changes of, like [`Position`].
+    /// Unless [`enable_property_cache`](Self::enable_property_cache) was called, which keeps its own cache that knows which ones those are.
+    pub async fn get<P>(&self, property: P) -> Result<P::Output, Error>
     where 
         P: Property,
         P::ParseAs: TryFrom<OwnedValue>
     {
-        let proxy = self.proxy(property.interface())?;
+        let value = match self.cached(&property) {
+            Some(value) => value,
+            None => {
+                let reply = self.connection.call_method(
+                    Some(self.name.as_ref()),
+                    "/org/mpris/MediaPlayer2",
+                    Some("org.freedesktop.DBus.Properties"),
+                    "Get",
+                    &(property.interface().as_str(), property.name())
+                ).await.map_err(|e| Error::for_property(e, property.name()))?;
 
-        let value: OwnedValue = proxy.get_property(property.name()).await?;
+                let value: OwnedValue = value::unwrap_variant(reply.body().deserialize()?);
+                self.cache_value(&property, &value);
+                value
+            }
+        };
 
-        // Create the intermediate type
-        let parsed: P::ParseAs = value
-            .try_into()
-            .map_err(|_e| zbus::Error::Variant(zbus::zvariant::Error::IncorrectType))?;
+        // Create the intermediate type, coercing the types close enough. Some broken players send a completely wrong type, like an array as the Metadata.
+        let signature = value.value_signature().to_string();
+        let parsed: P::ParseAs = value::parse(value).ok_or(Error::ParseFailed { property: property.name(), signature })?;
 
         Ok(property.into_output(parsed))
     }
 
-    /// Set a property that implements [`WritableProperty`].
-    pub async fn set<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), fdo::Error>
+    /// Reads every property of the interface with a single `GetAll` call, rather than one call per property.
+    /// <br>The values are unwrapped the same way as in [`get`](Self::get), so this is just as lenient.
+    pub async fn get_all(&self, interface: Interface) -> Result<HashMap<String, OwnedValue>, Error> {
+        let values = self.properties_proxy.get_all(interface.as_str().try_into()?).await?;
+
+        Ok(
+            values
+                .into_iter()
+                .map(|(name, value)| (name, value::unwrap_variant(value)))
+                .collect()
+        )
+    }
+
+    /// Reads every property this crate knows about, with a single `GetAll` call per interface, all of them at once.
+    /// <br>The properties the player doesn't provide are `None` (like every [`Interface::TrackList`] one, if it doesn't implement that), which makes this useful for feature detection, and for diagnostics.
+    pub async fn properties(&self) -> PlayerProperties {
+        let values = futures::future::join_all(Interface::ALL.map(async |interface| (interface, self.get_all(interface).await.unwrap_or_default()))).await;
+        let values = HashMap::from_iter(values);
+
+        PlayerProperties::new(&values)
+    }
+
+    /// Reads the [`Metadata`](properties::Metadata), but if it's [empty](Metadata::is_empty), retries up to `retries` times, waiting `delay` before each.
+    /// <br>Players briefly report empty metadata while changing tracks, so a single read can make the title flash blank. Returns the last read if it's still empty after the retries.
+    pub async fn get_metadata_stable(&self, retries: u32, delay: Duration) -> Result<Metadata, Error> {
+        let mut metadata = self.get(properties::Metadata).await?;
+
+        for _ in 0..retries {
+            if !metadata.is_empty() {
+                break;
+            }
+
+            tokio::time::sleep(delay).await;
+            metadata = self.get(properties::Metadata).await?;
+        }
+
+        Ok(metadata)
+    }
+
+    /// Like [`get`](Self::get), but returns `default` if the property can't be read for any reason.
+    /// <br>Handy for the optional properties, for example `player.get_or(CanControl, false).await`.
+    pub async fn get_or<P>(&self, property: P, default: P::Output) -> P::Output
+    where 
+        P: Property,
+        P::ParseAs: TryFrom<OwnedValue>
+    {
+        self.get(property).await.unwrap_or(default)
+    }
+
+    /// Set a property that implements [`WritableProperty`]. These don't depend on [`properties::CanControl`].
+    /// <br>Properties that do have to be set with [`set_controlled`](Self::set_controlled) instead:
+    /// ```compile_fail
+    /// # async fn volume(player: mpris_client_async::Player) {
+    /// player.set(mpris_client_async::properties::Volume, 0.5).await;
+    /// # }
+    /// ```
+    pub async fn set<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), Error>
     where 
         P: WritableProperty,
         P::ParseAs: 'a + Into<Value<'a>>
@@ -118,24 +388,103 @@ impl Player {
         let proxy = self.proxy(property.interface())?;
         let transformed_value: P::ParseAs = property.from_output(new_value);
 
-        proxy.set_property(property.name(), transformed_value).await.map(|_| ())
+        proxy.set_property(property.name(), transformed_value).await
+            .map_err(|e| Error::for_property(e.into(), property.name()))
     }
 
-
     /// Sets a property that requires the player to allow controlling, thus [`properties::CanControl`] must be true. 
-    pub async fn set_controlled<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), fdo::Error>
+    /// <br>Returns [`Error::NotControllable`] without writing anything if it's false. If it can't be read, the write is left for the player to judge.
+    /// <br>Properties that don't depend on it have to be set with [`set`](Self::set) instead:
+    /// ```compile_fail
+    /// # async fn fullscreen(player: mpris_client_async::Player) {
+    /// player.set_controlled(mpris_client_async::properties::Fullscreen, true).await;
+    /// # }
+    /// ```
+    pub async fn set_controlled<'a, P>(&self, property: P, new_value: P::Output) -> Result<(), Error>
     where 
         P: ControlWritableProperty,
         P::ParseAs: 'a + Into<Value<'a>>
     {
+        if !self.get_or(properties::CanControl, true).await {
+            return Err(Error::NotControllable { capability: properties::CanControl.name() });
+        }
+
         let proxy = self.proxy(property.interface())?;
         let transformed_value: P::ParseAs = property.from_output(new_value);
 
-        proxy.set_property(property.name(), transformed_value).await.map(|_| ())
+        proxy.set_property(property.name(), transformed_value).await
+            .map_err(|e| Error::for_property(e.into(), property.name()))
+    }
+
+    /// Reads [`Shuffle`](properties::Shuffle), returning `None` if the player doesn't support it (or it can't be read).
+    /// <br>This way a UI can hide the shuffle toggle, rather than showing a dead "off" button for players that don't shuffle at all.
+    pub async fn shuffle_state(&self) -> Option<bool> {
+        self.get(properties::Shuffle).await.ok()
+    }
+
+    /// Sets the [`LoopStatus`](properties::LoopStatus), then waits for the player to confirm it (with a `PropertiesChanged`).
+    /// <br>Returns false if the player didn't confirm it within `timeout`, as some players silently ignore the change.
+    /// Useful for toggles, that otherwise would show the optimistic, but wrong state.
+    pub async fn set_loop_verified(&self, value: Loop, timeout: Duration) -> Result<bool, Error> {
+        self.verify(properties::LoopStatus, value, self.set_controlled(properties::LoopStatus, value), timeout).await
+    }
+
+    /// Sets [`Shuffle`](properties::Shuffle), then waits for the player to confirm it, see [`set_loop_verified`](Self::set_loop_verified).
+    pub async fn set_shuffle_verified(&self, value: bool, timeout: Duration) -> Result<bool, Error> {
+        self.verify(properties::Shuffle, value, self.set_controlled(properties::Shuffle, value), timeout).await
+    }
+
+    /// Sets [`Fullscreen`](properties::Fullscreen). Fails with [`Error::NotControllable`] if [`properties::CanSetFullscreen`] is false,
+    /// instead of letting the player ignore it.
+    /// <br>That's the error of every capability check (like [`next`](Self::next) with [`properties::CanGoNext`]), so there's no separate one for the fullscreen.
+    pub async fn set_fullscreen(&self, value: bool) -> Result<(), Error> {
+        if !self.get_or(properties::CanSetFullscreen, false).await {
+            return Err(Error::NotControllable { capability: properties::CanSetFullscreen.name() });
+        }
+
+        self.set(properties::Fullscreen, value).await
+    }
+
+    /// Switches [`Fullscreen`](properties::Fullscreen) on or off, whichever it isn't now. Fails the same way as [`set_fullscreen`](Self::set_fullscreen).
+    pub async fn toggle_fullscreen(&self) -> Result<(), Error> {
+        let fullscreen = self.get(properties::Fullscreen).await?;
+
+        self.set_fullscreen(!fullscreen).await
+    }
+
+    /// Like [`set_fullscreen`](Self::set_fullscreen), but then waits for the player to confirm it, see [`set_loop_verified`](Self::set_loop_verified).
+    pub async fn set_fullscreen_verified(&self, value: bool, timeout: Duration) -> Result<bool, Error> {
+        self.verify(properties::Fullscreen, value, self.set_fullscreen(value), timeout).await
+    }
+
+    /// Runs `write`, then waits for the property to change to `expected`.
+    async fn verify<P, F>(&self, property: P, expected: P::Output, write: F, timeout: Duration) -> Result<bool, Error>
+    where
+        P: Property + Unpin + 'static,
+        P::ParseAs: TryFrom<OwnedValue>,
+        P::Output: PartialEq,
+        F: Future<Output = Result<(), Error>>
+    {
+        // Subscribe first, to not miss the confirmation
+        let raw = self.proxy(property.interface())?.receive_property_changed(property.name()).await;
+        let mut changes = ParsedPropertyStream::new(property, self.dbus_name(), raw);
+
+        write.await?;
+
+        let confirmed = async {
+            while let Some(change) = changes.next().await {
+                if change.value == expected {
+                    return true;
+                }
+            }
+            false
+        };
+
+        Ok(tokio::time::timeout(timeout, confirmed).await.unwrap_or(false))
     }
 
     /// Returns a stream that fires every time a property of some kind had been changed.
-    pub async fn subscribe_property_change<'a, P>(self: Arc<Self>, property: P) -> Result<ParsedPropertyStream<'a, P>, zbus::Error> 
+    pub async fn subscribe_property_change<'a, P>(self: Arc<Self>, property: P) -> Result<ParsedPropertyStream<'a, P>, Error> 
     where 
         P: Property + Unpin + 'static,
         P::ParseAs: TryFrom<OwnedValue>
@@ -145,8 +494,157 @@ impl Player {
         Ok(ParsedPropertyStream::new(property, self.dbus_name(), raw))
     }
 
+    /// Returns a stream of [`properties::CanControl`], so the controls of a UI can be enabled or disabled as it changes.
+    /// <br>The specs say it never changes (so most players never send the change), but some players do flip it, for example for DRM protected tracks.
+    /// <br>[`Player`] doesn't cache it anywhere, every [`get`](Self::get) reads the current value.
+    pub async fn subscribe_can_control<'a>(self: Arc<Self>) -> Result<ParsedPropertyStream<'a, properties::CanControl>, Error> {
+        self.subscribe_property_change(properties::CanControl).await
+    }
+
+    /// Returns a [`PlaybackEventStream`], that yields the [`PlaybackStatus`] changes, and a final [`PlayerGone`](streams::PlaybackEvent::PlayerGone) once the player leaves the bus.
+    /// <br>Prefer this over subscribing to [`PlaybackStatus`] directly, if a closed player should look different from a stopped one.
+    pub async fn subscribe_playback<'a>(self: Arc<Self>) -> Result<PlaybackEventStream<'a>, Error> {
+        let proxy = self.proxy(Interface::Player)?;
+        let owner = proxy.receive_owner_changed().await?;
+
+        Ok(PlaybackEventStream::new(self.dbus_name(), self.clone().subscribe_property_change(PlaybackStatus).await?, owner))
+    }
+
+    /// Returns a [`MetadataDiffStream`] that yields which fields of the [`Metadata`] changed every time the player sends a new one.
+    /// <br>Useful for debugging a player's behaviour, or for UIs that only want to animate what changed.
+    pub async fn subscribe_metadata_diff<'a>(self: Arc<Self>) -> Result<MetadataDiffStream<'a>, Error> {
+        Ok(MetadataDiffStream::new(self.subscribe_property_change(properties::Metadata).await?))
+    }
+
+    /// Returns a stream of the metadata dictionaries exactly as the player sent them, without parsing them into [`Metadata`] (the first item is the current one).
+    /// <br>Useful for bridges and proxies, that have to relay every key, including the ones [`Metadata`] doesn't know about.
+    pub async fn subscribe_metadata_raw<'a>(self: Arc<Self>) -> Result<impl Stream<Item = StreamYield<HashMap<String, OwnedValue>>> + 'a, Error> {
+        Ok(
+            self.subscribe_property_change(properties::Metadata).await?
+                .map(|metadata| StreamYield::new(metadata.player_name, metadata.value.raw))
+        )
+    }
+
+    /// Returns a stream of the [`art_url`](Metadata::art_url), that only yields when it changes (the first item is the current one).
+    /// <br>Players often fetch the art after the rest of the metadata, this way only the art has to be updated when it arrives.
+    pub async fn subscribe_art_url<'a>(self: Arc<Self>) -> Result<impl Stream<Item = StreamYield<Option<String>>> + 'a, Error> {
+        let mut last: Option<Option<String>> = None;
+
+        Ok(
+            self.subscribe_property_change(properties::Metadata).await?
+                .filter_map(move |metadata| {
+                    let art_url = metadata.value.art_url;
+                    let changed = last.as_ref() != Some(&art_url);
+                    last = Some(art_url.clone());
+
+                    futures::future::ready(changed.then(|| StreamYield::new(metadata.player_name, art_url)))
+                })
+        )
+    }
+
+    /// Returns a stream of the [`Metadata`], that only yields when the track changes (the first item is the current track).
+    /// <br>Metadata refreshes of the same track (like the art arriving late) are skipped, which is what scrobblers need.
+    /// <br>Tracks are told apart by their [`trackid`](Metadata::trackid), or if the player doesn't provide one, by the title, artists and album.
+    pub async fn track_changes<'a>(self: Arc<Self>) -> Result<impl Stream<Item = StreamYield<Metadata>> + 'a, Error> {
+        let mut last: Option<(Option<TrackId>, String, Vec<String>, String)> = None;
+
+        Ok(
+            self.subscribe_property_change(properties::Metadata).await?
+                .filter_map(move |metadata| {
+                    let track = &metadata.value;
+                    let key = match &track.trackid {
+                        Some(trackid) => (Some(trackid.clone()), String::new(), Vec::new(), String::new()),
+                        None => (None, track.title.clone(), track.artists.clone(), track.album.clone())
+                    };
+                    let changed = last.as_ref() != Some(&key);
+                    last = Some(key);
+
+                    futures::future::ready(changed.then_some(metadata))
+                })
+        )
+    }
+
+    /// Returns a stream of the [`Volume`](properties::Volume), that instead of jumping to a new value, ramps to it linearly over `ramp`, yielding about 60 times a second meanwhile.
+    /// <br>Meant for OSD overlays and sliders, that would look jerky with the many changes of quickly pressed volume keys.
+    /// A change arriving mid-ramp starts a new ramp from wherever the previous one was at. The first item is the current volume, as is.
+    /// <br><br>Use [`subscribe_property_change`](Self::subscribe_property_change) for the exact values.
+    pub async fn subscribe_volume_smoothed<'a>(self: Arc<Self>, ramp: Duration) -> Result<impl Stream<Item = StreamYield<f64>> + 'a, Error> {
+        struct Smoothing<S> {
+            volumes: S,
+            player_name: OwnedBusName,
+            current: Option<f64>,
+            /// From, to, and when it started
+            ramp: Option<(f64, f64, tokio::time::Instant)>,
+        }
+
+        let state = Smoothing {
+            volumes: Box::pin(self.clone().subscribe_property_change(properties::Volume).await?),
+            player_name: self.dbus_name(),
+            current: None,
+            ramp: None,
+        };
+
+        Ok(futures::stream::unfold(state, move |mut state| async move {
+            loop {
+                let new = match state.ramp {
+                    Some((from, to, started)) => tokio::select! {
+                        new = state.volumes.next() => new?.value,
+                        _ = tokio::time::sleep(SMOOTHING_INTERVAL) => {
+                            let progress = match ramp.is_zero() {
+                                true => 1.0,
+                                false => (started.elapsed().as_secs_f64() / ramp.as_secs_f64()).min(1.0)
+                            };
+                            let current = from + (to - from) * progress;
+
+                            state.current = Some(current);
+                            if progress >= 1.0 {
+                                state.ramp = None;
+                            }
+                            return Some((StreamYield::new(state.player_name.clone(), current), state));
+                        }
+                    },
+                    None => state.volumes.next().await?.value
+                };
+
+                match state.current {
+                    Some(current) if current != new => state.ramp = Some((current, new, tokio::time::Instant::now())),
+                    Some(_) => state.ramp = None,
+                    None => {
+                        state.current = Some(new);
+                        return Some((StreamYield::new(state.player_name.clone(), new), state));
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Returns a [`SharedStream`] of the [`Metadata`] changes. Unlike [`subscribe_property_change`](Self::subscribe_property_change), 
+    /// this can be cloned, and all the clones are fed by the same single subscription.
+    /// <br>Useful when several widgets are interested in the same player, as each subscription adds a match rule to the bus.
+    /// <br><br>The first item of every clone created right away is the current metadata. Must be called within a tokio runtime.
+    pub async fn shared_metadata_stream(self: Arc<Self>) -> Result<SharedStream<StreamYield<Metadata>>, Error> {
+        let source = self.subscribe_property_change(properties::Metadata).await?;
+        Ok(SharedStream::new(source, SHARED_STREAM_CAPACITY))
+    }
+
+    /// Returns a [`PlayerUpdateStream`], yielding everything that happens to the player: playback, metadata, volume, rate, loop and shuffle changes, and seeks.
+    /// <br>Handy when the player has to be mirrored as a whole, rather than subscribing to each of these one by one.
+    pub async fn events<'a>(self: Arc<Self>) -> Result<PlayerUpdateStream<'a>, Error> {
+        Ok(
+            PlayerUpdateStream::new(
+                self.clone().subscribe_property_change(PlaybackStatus).await?,
+                self.clone().subscribe_property_change(properties::Metadata).await?,
+                self.clone().subscribe_property_change(properties::Volume).await?,
+                self.clone().subscribe_property_change(Rate).await?,
+                self.clone().subscribe_property_change(properties::LoopStatus).await?,
+                self.clone().subscribe_property_change(properties::Shuffle).await?,
+                self.subscribe(Seeked).await?
+            )
+        )
+    }
+
     /// Subscribe to a D-Bus signal. Possible options: [`signals`]
-    pub async fn subscribe<'a, S>(self: Arc<Self>, signal: S) -> Result<ParsedSignalStream<'a, S>, zbus::Error>
+    pub async fn subscribe<'a, S>(self: Arc<Self>, signal: S) -> Result<ParsedSignalStream<'a, S>, Error>
     where
         S: Signal + Unpin + 'static,
         S::ParseAs: TryFrom<OwnedValue>
@@ -157,113 +655,420 @@ impl Player {
         Ok(ParsedSignalStream::new(signal, self.dbus_name(), raw))
     }
 
+    /// Waits for the next [`Seeked`], and returns the position the player seeked to.
+    /// <br>A shorthand for subscribing to [`Seeked`] for a single event. Fails if the player leaves the bus before seeking.
+    pub async fn next_seek(self: Arc<Self>) -> Result<Duration, Error> {
+        let mut seeked = self.subscribe(Seeked).await?;
+
+        match seeked.next().await {
+            Some(position) => Ok(position.value),
+            None => Err(Error::PlayerGone)
+        }
+    }
+
+    /// Returns a stream of every signal the player emits on the MPRIS [interfaces](Interface), through a single match rule.
+    /// <br>Cheaper than a [`subscribe`](Self::subscribe) for each signal, when several are needed. Signals this crate has no type for are yielded as [`AnySignal::Other`](signals::AnySignal::Other).
+    /// <br><br>The signals are matched against the connection currently owning the player's name, so if the player restarts, this has to be called again.
+    pub async fn subscribe_all_signals<'a>(self: Arc<Self>) -> Result<impl Stream<Item = StreamYield<signals::AnySignal>> + 'a, Error> {
+        // A well known sender can't be matched locally, which would let in the signals of the other players with the same path
+        let sender: zbus::names::BusName = match self.unique_name().await {
+            Ok(unique_name) => unique_name.into_inner().into(),
+            Err(_) => self.name.clone().into_inner()
+        };
+
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(sender)?
+            .path("/org/mpris/MediaPlayer2")?
+            .build();
+        let messages = zbus::MessageStream::for_match_rule(rule, &self.connection, None).await?;
+
+        let player_name = self.dbus_name();
+        Ok(
+            messages.filter_map(move |message| {
+                let signal = message.ok().and_then(signals::AnySignal::from_message);
+                futures::future::ready(signal.map(|signal| StreamYield::new(player_name.clone(), signal)))
+            })
+        )
+    }
 
     /// Returns a [`PositionStream`] that yields the current (esitmated) position of the media playback. 
     /// It does this by listening to the [`Seeked`] [`signal`](Signal) and the [`PlaybackStatus`] and [`Rate`] [`properties`](Property), and those's changes
     /// to determine the position of the playback.
     /// 
     /// <br><br>This SHOULD be prefered over repetitively calling [`get`](Self::get), as this is much more lighter.
-    pub async fn subscribe_position<'a, 'b>(self: Arc<Self>) -> Result<PositionStream<'a>, zbus::Error> {
+    /// <br>For players that don't emit [`Seeked`] reliably, use [`PositionStream::with_resync`].
+    /// <br><br>The stream is seeded with the current [`PlaybackStatus`], [`Rate`] and [`Position`], so the first yielded value is the actual position, rather than zero.
+    /// <br>Every subscription is live before these are read, so a change happening at any point after this is called is either in the seed, or yielded by the stream.
+    /// <br><br>Fails if the player can't report its [`Position`] (some only support play/pause), rather than returning a stream that never updates. See [`supports_position`](Self::supports_position).
+    pub async fn subscribe_position<'a>(self: Arc<Self>) -> Result<PositionStream<'a>, Error> {
+        let playback_stream = self.clone().subscribe_property_change(PlaybackStatus).await?;
+        let rate_stream = self.clone().subscribe_property_change(Rate).await?;
+        let seeked_stream = self.clone().subscribe(Seeked).await?;
+
         Ok(
             PositionStream::new(
-                self.dbus_name(),
-                self.clone().subscribe_property_change(PlaybackStatus).await?, 
+                self.clone(),
+                playback_stream,
                 self.get(PlaybackStatus).await?,
-                self.clone().subscribe_property_change(Rate).await?,
+                rate_stream,
                 self.get(Rate).await?,
-                self.clone().subscribe(Seeked).await?,
+                seeked_stream,
                 self.get(Position).await?,
             )
         )
     }
-    
 
+    /// Like [`subscribe_position`](Self::subscribe_position), but the positions are clamped to the length of the track, 
+    /// and [`PositionEvent::Ended`](streams::PositionEvent::Ended) is yielded when the playback reaches it. Useful for scrubbers, so the bar doesn't overshoot.
+    /// <br>If the [`Metadata`](properties::Metadata) has no length, this behaves like [`subscribe_position`](Self::subscribe_position).
+    pub async fn subscribe_position_bounded<'a>(self: Arc<Self>) -> Result<BoundedPositionStream<'a>, Error> {
+        let metadata_stream = self.clone().subscribe_property_change(properties::Metadata).await?;
+        let position_stream = self.clone().subscribe_position().await?;
+
+        Ok(BoundedPositionStream::new(position_stream, metadata_stream, self.get(properties::Metadata).await?))
+    }
+
+    /// Returns a [`NowPlayingStream`], that yields the [`Metadata`], the (estimated) position and the [`Playback`] status together, every time any of them changes.
+    /// <br>The position is tracked the same way as in [`subscribe_position`](Self::subscribe_position).
+    pub async fn subscribe_now_playing<'a>(self: Arc<Self>) -> Result<NowPlayingStream<'a>, Error> {
+        Ok(
+            NowPlayingStream::new(
+                self.dbus_name(),
+                self.clone().subscribe_position().await?,
+                self.clone().subscribe_property_change(properties::Metadata).await?,
+                self.get(properties::Metadata).await?,
+                self.clone().subscribe_property_change(PlaybackStatus).await?,
+                self.get(PlaybackStatus).await?
+            )
+        )
+    }
+
+    /// Returns a [`PollingPositionStream`] that reads the [`Position`] every `interval`.
+    /// <br>Only use this for players that never report their changes, as [`subscribe_position`](Self::subscribe_position) is much lighter.
+    /// Whether a player can seek at all can be checked with [`properties::CanSeek`].
+    pub fn subscribe_position_polling(self: Arc<Self>, interval: Duration) -> PollingPositionStream {
+        PollingPositionStream::new(self, interval)
+    }
 
     //                             ====================
     //                             ===    METHODS   ===
     //                             ====================
 
     
-    async fn call_method<A, R>(&self, method_name: &str, arguments: A, iface: Interface) -> Result<R, zbus::Error> 
+    async fn call_method<A, R>(&self, method_name: &str, arguments: A, iface: Interface) -> Result<R, Error> 
     where 
-        A: serde::Serialize + zbus::zvariant::DynamicType,
+        A: zbus::export::serde::Serialize + zbus::zvariant::DynamicType,
         R: for<'d> zbus::zvariant::DynamicDeserialize<'d>,
     {
-        let proxy = Proxy::new(&self.connection, self.name.to_owned(), "/org/mpris/MediaPlayer2", iface.as_str()).await?;
+        // The cached proxies can be shared between concurrent calls, only build one if that interface's failed to
+        let proxy = match self.proxy(iface) {
+            Ok(proxy) => proxy.clone(),
+            Err(_) => proxy::Builder::new(&self.connection)
+                .destination(self.name.to_owned())?
+                .path("/org/mpris/MediaPlayer2")?
+                .interface(iface.as_str())?
+                .cache_properties(proxy::CacheProperties::No)
+                .build()
+                .await?
+        };
 
-        proxy.call(method_name, &arguments).await
+        Ok(proxy.call(method_name, &arguments).await?)
+    }
+
+    /// Asks the player to quit. It may still refuse (for example the user cancels it), and if [`properties::CanQuit`] is false this has no effect.
+    /// <br>Some players drop their connection the moment they receive this, without replying. As the player is gone either way, that is treated as success.
+    /// <br>A missing reply (or a broken connection) is only treated so if the bus confirms that the player's name has no owner anymore,
+    /// as a player that hangs doesn't reply either.
+    pub async fn quit(&self) -> Result<(), Error> {
+        let unanswered = |error: &Error| match error {
+            Error::Dbus(zbus::Error::MethodError(name, _, _)) => name.as_str() == "org.freedesktop.DBus.Error.NoReply",
+            Error::Dbus(zbus::Error::InputOutput(_)) => true,
+            _ => false
+        };
+
+        match self.call_method("Quit", (), Interface::MediaPlayer2).await {
+            Err(Error::PlayerGone) => Ok(()),
+            Err(error) if unanswered(&error) => {
+                let dbus = fdo::DBusProxy::new(&self.connection).await?;
+                match dbus.name_has_owner(self.name.as_ref()).await {
+                    Ok(false) => Ok(()),
+                    _ => Err(error)
+                }
+            },
+            result => result
+        }
     }
 
     /// Skips to the next track in the tracklist. If there is no next track (and endless playback and track repeat are both off), stop playback.
     /// <br>If playback is paused or stopped, it remains that way.
-    /// <br>If [`properties::CanGoNext`] is false, attempting to call this method should have no effect.
-    pub async fn next(&self) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanGoNext`] is false.
+    pub async fn next(&self) -> Result<(), Error> {
+        self.require(properties::CanGoNext).await?;
         self.call_method("Next", [()], Interface::Player).await
     }
 
     /// Skips to the previous track in the tracklist. If there is no previous track (and endless playback and track repeat are both off), stop playback.
     /// <br>If playback is paused or stopped, it remains that way.
-    /// <br>If [`properties::CanGoPrevious`] is false, attempting to call this method should have no effect.
-    pub async fn previous(&self) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanGoPrevious`] is false.
+    pub async fn previous(&self) -> Result<(), Error> {
+        self.require(properties::CanGoPrevious).await?;
         self.call_method("Previous", [()], Interface::Player).await
     }
 
     /// Pauses the playback. 
-    /// If [`properties::CanPause`] is false, this should have no effect.
-    pub async fn pause(&self) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanPause`] is false. If it can't be read, the call is left for the player to judge.
+    pub async fn pause(&self) -> Result<(), Error> {
+        self.require(properties::CanPause).await?;
         self.call_method("Pause", [()], Interface::Player).await
     }
 
-    /// Starts or resumes the playback. 
-    /// <br>If playback is already running or if [`properties::CanPlay`] is false, this should have no effect.
-    pub async fn play(&self) -> Result<(), zbus::Error> {
+    /// Starts or resumes the playback. If playback is already running, this should have no effect.
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanPlay`] is false.
+    pub async fn play(&self) -> Result<(), Error> {
+        self.require(properties::CanPlay).await?;
         self.call_method("Play", [()], Interface::Player).await
     }
 
     /// Toggles the playback status between play and pause.
-    /// <br>If [`properties::CanPause`] is false, this should have no effect, and may return with an error.
-    pub async fn play_pause(&self) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanPause`] is false.
+    pub async fn play_pause(&self) -> Result<(), Error> {
+        self.require(properties::CanPause).await?;
         self.call_method("PlayPause", [()], Interface::Player).await
     }
 
     /// Stops playback. Calling [`Self::play`] after this should restart the playlist.
-    /// <br>If [`properties::CanControl`] is false, calling this should have no effect, and may raise an error.
-    pub async fn stop(&self) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanControl`] is false.
+    pub async fn stop(&self) -> Result<(), Error> {
+        self.require(properties::CanControl).await?;
         self.call_method("Stop", [()], Interface::Player).await
     }
 
+    /// Fails if the capability is false. Players that don't report it get the benefit of the doubt, as plenty of them refuse silently anyway.
+    async fn require<P>(&self, capability: P) -> Result<(), Error>
+    where
+        P: Property<Output = bool>,
+        P::ParseAs: TryFrom<OwnedValue>
+    {
+        let name = capability.name();
+
+        match self.get_or(capability, true).await {
+            true => Ok(()),
+            false => Err(Error::NotControllable { capability: name })
+        }
+    }
+
     /// A duration to seek forward, or of backwards is true backwards. 
-    /// <br>May only be used if [`properties::CanSeek`] is true.
-    pub async fn seek(&self, duration: Duration, backwards: bool) -> Result<(), zbus::Error> {
-        let modified_time = duration.as_micros() as f64 * {if backwards {-1.0} else {1.0}};
-        self.call_method("Seek", [modified_time], Interface::Player).await
+    /// <br>Seeking before the start of the track goes to the start, and seeking past its end acts like [`next`](Self::next).
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanSeek`] is false.
+    pub async fn seek(&self, duration: Duration, backwards: bool) -> Result<(), Error> {
+        self.require(properties::CanSeek).await?;
+
+        let offset = duration_to_micros(duration) * if backwards { -1 } else { 1 };
+        self.call_method("Seek", (offset,), Interface::Player).await
     }
 
     /// Sets the position of the track between 0 and the [length of the track](metadata::Metadata::length). track_id can be retreived from the [metadata](metadata::Metadata::trackid), but it may <b>NOT</b> be "/org/mpris/MediaPlayer2/TrackList/NoTrack".
-    /// <br>If position is greater than the [length of the track](metadata::Metadata::length), this shouldn't do anything. 
+    /// <br>If position is greater than the [length of the track](metadata::Metadata::length), this does nothing, as the specs say. It's checked here too, as not every player ignores it.
     /// <br>If [properties::CanSeek] is false this should have no effect.
-    pub async fn set_position(&self, track_id: String, position: Duration) -> Result<(), zbus::Error> {
-        self.call_method("SetPosition", [track_id, position.as_micros().to_string()], Interface::Player).await
+    /// <br>Fails with [`Error::InvalidTrackId`] without calling the player if `track_id` is empty (which is what players that omit the trackid would need), or not an object path.
+    /// <br><br>`track_id` can be the [trackid](metadata::Metadata::trackid) of the metadata directly, like `player.set_position(&trackid, position)`.
+    pub async fn set_position(&self, track_id: impl AsRef<str>, position: Duration) -> Result<(), Error> {
+        let track_id = track_id.as_ref();
+        if track_id.is_empty() {
+            return Err(Error::InvalidTrackId(String::new()));
+        }
+
+        let track_id = tracklist::track_path(track_id)?;
+
+        let length = self.get(properties::Metadata).await.ok().and_then(|metadata| metadata.length);
+        if length.is_some_and(|length| position > length) {
+            return Ok(());
+        }
+
+        self.call_method("SetPosition", (track_id, duration_to_micros(position)), Interface::Player).await
+    }
+
+    /// Seeks to `position` in the current track. This is what most UIs want instead of [`set_position`](Self::set_position), as the trackid is resolved automatically.
+    /// <br>If the player doesn't provide a (valid) trackid, or doesn't implement SetPosition, it falls back to a relative [`seek`](Self::seek) from the current [`Position`].
+    /// <br>Returns an error if there is no track currently ([`NO_TRACK`]).
+    pub async fn seek_to(&self, position: Duration) -> Result<(), Error> {
+        let metadata = self.get(properties::Metadata).await?;
+
+        if metadata.trackid.as_deref() == Some(NO_TRACK) {
+            return Err(Error::NoTrack);
+        }
+
+        // Some players send trackids that aren't object paths, these can only be seeked relatively
+        if let Some(trackid) = metadata.trackid_path() {
+            match self.set_position(trackid.as_str(), position).await {
+                Err(Error::Dbus(zbus::Error::MethodError(name, _, _))) 
+                    if name.as_str() == "org.freedesktop.DBus.Error.UnknownMethod" || name.as_str() == "org.freedesktop.DBus.Error.NotSupported" => {},
+                result => return result
+            }
+        }
+
+        let current = self.get(Position).await?;
+        if position >= current {
+            self.seek(position - current, false).await
+        } else {
+            self.seek(current - position, true).await
+        }
+    }
+
+    /// Like [`seek_to`](Self::seek_to), but also confirms that the jump landed, as plenty of players ignore `SetPosition` or clamp the position.
+    /// <br>Returns true if the player reported a [`Seeked`] to about `position` within `timeout`. If it didn't report any, the [`Position`] is read instead.
+    /// Returns false if it seeked somewhere else, or the position doesn't match.
+    pub async fn set_position_verified(&self, position: Duration, timeout: Duration) -> Result<bool, Error> {
+        // Subscribe first to not miss the signal
+        let raw = self.proxy(Interface::Player)?.receive_signal(Seeked.name()).await?;
+        let mut seeked = ParsedSignalStream::new(Seeked, self.dbus_name(), raw);
+        let started = tokio::time::Instant::now();
+
+        self.seek_to(position).await?;
+
+        if let Ok(Some(landed)) = tokio::time::timeout(timeout, seeked.next()).await {
+            return Ok(landed.value.abs_diff(position) <= SEEK_TOLERANCE);
+        }
+
+        // The player may have kept playing since the seek
+        let current = self.get(Position).await?;
+        let rate = self.get_or(Rate, 1.0).await;
+        let latest = micros::advance(position, started.elapsed(), rate);
+
+        Ok(current + SEEK_TOLERANCE >= position && current <= latest + SEEK_TOLERANCE)
+    }
+
+    /// If the position of the player means anything, so a progress bar makes sense: the [`Position`] can be read, and the current track has a [length](Metadata::length).
+    /// <br>True doesn't mean the player can be seeked, that's [`properties::CanSeek`]. It isn't required here, as plenty of players report their position without being seekable.
+    pub async fn supports_position(&self) -> bool {
+        let (position, metadata) = futures::join!(self.get(Position), self.get(properties::Metadata));
+
+        position.is_ok() && metadata.is_ok_and(|metadata| metadata.length.is_some())
+    }
+
+    /// Returns how far the playback is in the current track, between 0.0 and 1.0, for sliders.
+    /// <br>Returns `None` if the length of the track is unknown (like for live streams), or any of the properties can't be read.
+    pub async fn position_fraction(&self) -> Option<f64> {
+        let length = self.get(properties::Metadata).await.ok()?.length.filter(|length| !length.is_zero())?;
+        let position = self.get(Position).await.ok()?;
+
+        Some((position.as_secs_f64() / length.as_secs_f64()).clamp(0.0, 1.0))
     }
 
-    /// Opens a URI, which's scheme should be an element of [`properties::SupportedURIs`] and the mime-type should match one of the elements of [properties::SupportedMIMEs]. 
+    /// Returns how much (wall-clock) time is left from the current track, taking the [`Rate`] into account, as in `(length - position) / rate`.
+    /// <br>Returns `None` if the length of the track is unknown (like for live streams), the position can't be read, or the rate is zero (the track never ends).
+    /// The same goes for a rate so close to zero that the time left doesn't fit in a [`Duration`].
+    /// A position past the length counts as nothing left. If the rate can't be read, it's taken as 1.0.
+    pub async fn time_remaining(&self) -> Option<Duration> {
+        let (metadata, position, rate) = futures::join!(self.get(properties::Metadata), self.get(Position), self.get(Rate));
+
+        let length = metadata.ok()?.length?;
+        let rate = rate.unwrap_or(1.0);
+        if !rate.is_finite() || rate <= 0.0 {
+            return None;
+        }
+
+        let remaining = length.saturating_sub(position.ok()?);
+        Duration::try_from_secs_f64(remaining.as_secs_f64() / rate).ok()
+    }
+
+    /// Seeks to `fraction` (between 0.0 and 1.0, clamped) of the current track with [`seek_to`](Self::seek_to), for sliders.
+    /// <br>Returns an error if the length of the track is unknown (like for live streams).
+    pub async fn seek_to_fraction(&self, fraction: f64) -> Result<(), Error> {
+        let Some(length) = self.get(properties::Metadata).await?.length.filter(|length| !length.is_zero()) else {
+            return Err(Error::UnknownLength);
+        };
+
+        let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+        self.seek_to(length.mul_f64(fraction)).await
+    }
+
+    /// Executes the [`PlayerAction`]s one after the other, stopping at the first one that fails. Useful for automation, like "open this, seek to 0:30, set the volume to half, and play".
+    /// <br>After [`PlayerAction::OpenUri`], the next action waits until the player loads the track (meaning the [`Metadata`] changes), so seeking applies to the new one.
+    /// If the player doesn't report it within a few seconds, the actions continue anyway.
+    pub async fn apply(&self, actions: &[PlayerAction]) -> Result<(), Error> {
+        for action in actions {
+            match action {
+                PlayerAction::OpenUri(uri) => {
+                    // Subscribe first to not miss the new track. The first item is the current one.
+                    let raw = self.proxy(Interface::Player)?.receive_property_changed(properties::Metadata.name()).await;
+                    let mut tracks = ParsedPropertyStream::new(properties::Metadata, self.dbus_name(), raw);
+                    let current = self.get(properties::Metadata).await.ok();
+
+                    self.open_uri(uri).await?;
+
+                    let loaded = async {
+                        while let Some(track) = tracks.next().await {
+                            if Some(&track.value) != current.as_ref() {
+                                break;
+                            }
+                        }
+                    };
+                    _ = tokio::time::timeout(LOAD_TIMEOUT, loaded).await;
+                },
+                PlayerAction::SeekTo(position) => self.seek_to(*position).await?,
+                PlayerAction::SetVolume(volume) => self.set_controlled(properties::Volume, *volume).await?,
+                PlayerAction::SetRate(rate) => self.set_controlled(Rate, *rate).await?,
+                PlayerAction::Play => self.play().await?,
+                PlayerAction::Pause => self.pause().await?,
+                PlayerAction::Stop => self.stop().await?,
+                PlayerAction::Next => self.next().await?,
+                PlayerAction::Previous => self.previous().await?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The [`properties::SupportedURIs`] as [`UriScheme`]s, so they can be matched on rather than compared as strings.
+    pub async fn supported_uri_schemes(&self) -> Result<Vec<UriScheme>, Error> {
+        Ok(self.get(properties::SupportedURIs).await?.into_iter().map(UriScheme::from).collect())
+    }
+
+    /// If the player can open URIs with the `scheme` (like `"file"`, or `"https"`), according to [`properties::SupportedURIs`].
+    /// <br>The comparison ignores case, and a trailing `://` on `scheme`. Returns false if the property can't be read.
+    pub async fn supports_scheme(&self, scheme: &str) -> bool {
+        let scheme = scheme.trim_end_matches("://");
+
+        self.get_or(properties::SupportedURIs, Vec::new()).await
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(scheme))
+    }
+
+    /// Opens a URI, which's scheme should be an element of [`properties::SupportedURIs`] (see [`supports_scheme`](Self::supports_scheme)) and the mime-type should match one of the elements of [properties::SupportedMIMEs]. 
     /// If not supported it should raise an error.
     /// <br>If the playback is stopped, it should be started. It also shouldnt be assumed the player opens the URI as soon as called!
-    pub async fn open_uri(&self, uri: String) -> Result<(), zbus::Error> {
+    /// <br>Returns [`Error::UnsupportedUri`] without calling the player if it has no scheme, or the scheme isn't one of the [`properties::SupportedURIs`].
+    /// Players that don't list any schemes are left to judge it themselves.
+    pub async fn open_uri(&self, uri: impl AsRef<str>) -> Result<(), Error> {
+        let uri = uri.as_ref();
+        let Some((scheme, _)) = uri.split_once(':') else {
+            return Err(Error::UnsupportedUri(uri.to_string()));
+        };
+
+        let supported = self.get_or(properties::SupportedURIs, Vec::new()).await;
+        if !supported.is_empty() && !supported.iter().any(|supported| supported.eq_ignore_ascii_case(scheme)) {
+            return Err(Error::UnsupportedUri(uri.to_string()));
+        }
+
         self.call_method("OpenUri", [uri], Interface::Player).await
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zbus::zvariant::ObjectPath;
 
     #[test]
     fn playback_status_conversion() {
         assert_eq!(Playback::Playing, Playback::from("Playing"));
         assert_eq!(Playback::Paused, Playback::from("Paused"));
         assert_eq!(Playback::Stopped, Playback::from("Stopped"));
+
+        assert_eq!(Playback::Playing, Playback::from(" playing "));
+        assert_eq!(Playback::Paused, Playback::from(String::from("PAUSED\n")));
     }
 
     #[test]
@@ -271,5 +1076,1560 @@ mod tests {
         assert_eq!(Loop::Playlist, Loop::from("Playlist"));
         assert_eq!(Loop::None, Loop::from("None"));
         assert_eq!(Loop::Track, Loop::from("Track"));
+
+        assert_eq!(Loop::Playlist, Loop::from("\tplayList "));
+        assert_eq!(Loop::Track, Loop::from(String::from(" TRACK")));
+    }
+
+    #[test]
+    fn property_interfaces() {
+        use properties::*;
+
+        // org.mpris.MediaPlayer2
+        assert_eq!(CanQuit.interface(), Interface::MediaPlayer2);
+        assert_eq!(Fullscreen.interface(), Interface::MediaPlayer2);
+        assert_eq!(CanSetFullscreen.interface(), Interface::MediaPlayer2);
+        assert_eq!(CanRaise.interface(), Interface::MediaPlayer2);
+        assert_eq!(HasTrackList.interface(), Interface::MediaPlayer2);
+        assert_eq!(Identity.interface(), Interface::MediaPlayer2);
+        assert_eq!(DesktopEntry.interface(), Interface::MediaPlayer2);
+        assert_eq!(SupportedURIs.interface(), Interface::MediaPlayer2);
+        assert_eq!(SupportedMIMEs.interface(), Interface::MediaPlayer2);
+
+        // org.mpris.MediaPlayer2.Player
+        assert_eq!(PlaybackStatus.interface(), Interface::Player);
+        assert_eq!(LoopStatus.interface(), Interface::Player);
+        assert_eq!(Rate.interface(), Interface::Player);
+        assert_eq!(Shuffle.interface(), Interface::Player);
+        assert_eq!(Metadata.interface(), Interface::Player);
+        assert_eq!(Volume.interface(), Interface::Player);
+        assert_eq!(Position.interface(), Interface::Player);
+        assert_eq!(MinimumRate.interface(), Interface::Player);
+        assert_eq!(MaximumRate.interface(), Interface::Player);
+        assert_eq!(CanGoNext.interface(), Interface::Player);
+        assert_eq!(CanGoPrevious.interface(), Interface::Player);
+        assert_eq!(CanPlay.interface(), Interface::Player);
+        assert_eq!(CanPause.interface(), Interface::Player);
+        assert_eq!(CanSeek.interface(), Interface::Player);
+        assert_eq!(CanControl.interface(), Interface::Player);
+
+        // org.mpris.MediaPlayer2.TrackList
+        assert_eq!(Tracks.interface(), Interface::TrackList);
+        assert_eq!(CanEditTracks.interface(), Interface::TrackList);
+
+        // org.mpris.MediaPlayer2.Playlists
+        assert_eq!(PlaylistCount.interface(), Interface::Playlists);
+        assert_eq!(Orderings.interface(), Interface::Playlists);
+        assert_eq!(ActivePlaylist.interface(), Interface::Playlists);
+
+        assert_eq!(signals::Seeked.interface(), Interface::Player);
+    }
+
+    #[test]
+    fn metadata_diff() {
+        let old = Metadata::from(mock::metadata_with_trackid("/track/1"));
+        let mut new = old.clone();
+        assert!(old.diff(&new).is_empty());
+
+        new.title = String::from("Title");
+        new.art_url = Some(String::from("file:///cover.png"));
+        assert_eq!(old.diff(&new).changed, vec!["art_url", "title"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_polling() {
+        let bus = mock::PrivateBus::start();
+        let (player, server) = bus.connect(mock::MockPlayer { position: 5_000_000, ..Default::default() }).await;
+        let polling = Arc::new(player).subscribe_position_polling(Duration::from_secs(2));
+        futures::pin_mut!(polling);
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(polling.next().await.unwrap().value, Duration::from_secs(5));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.position = 7_000_000;
+        assert_eq!(polling.next().await.unwrap().value, Duration::from_secs(7));
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+        assert_eq!(polling.next().await.unwrap().value, Duration::from_secs(7));
+        assert_eq!(start.elapsed(), Duration::from_secs(4));
+
+        // Failed reads are skipped, and the stream goes on. The timing isn't checked, as a read may be in flight when the timeout fires.
+        iface.get_mut().await.position_unsupported = true;
+        assert!(tokio::time::timeout(Duration::from_secs(3), polling.next()).await.is_err());
+        iface.get_mut().await.position_unsupported = false;
+        assert_eq!(polling.next().await.unwrap().value, Duration::from_secs(7));
+
+        drop(iface);
+        server.close().await.unwrap();
+        assert!(polling.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn quit_tolerates_dropped_connection() {
+        let bus = mock::PrivateBus::start();
+        let mock = mock::MockPlayer { drop_on_quit: true, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = bus.connect(mock).await;
+
+        player.quit().await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Quit"]);
+
+        // Without a bus, nothing confirms the player is gone, as it might be just the connection that broke
+        let (player, _server) = mock::connect(mock::MockPlayer { drop_on_quit: true, ..Default::default() }).await;
+        assert!(matches!(player.quit().await, Err(Error::Dbus(_))));
+    }
+
+    #[tokio::test]
+    async fn quit_hung_player() {
+        let bus = mock::PrivateBus::start();
+        let (player, _server) = bus.connect(mock::MockPlayer { hang_on_quit: true, ..Default::default() }).await;
+
+        let client = zbus::connection::Builder::address(bus.address.as_str()).unwrap()
+            .method_timeout(Duration::from_millis(200))
+            .build().await.unwrap();
+        let player = Player::new(player.dbus_name(), client).await.unwrap();
+
+        // The player is still on the bus, so it didn't quit
+        assert!(matches!(player.quit().await, Err(Error::Dbus(_))));
+
+        let (player, _server) = bus.connect_as("org.mpris.MediaPlayer2.other", mock::MockPlayer { no_reply_on_quit: true, ..Default::default() }).await;
+        assert!(matches!(player.quit().await, Err(Error::Dbus(zbus::Error::MethodError(name, _, _))) if name.as_str() == "org.freedesktop.DBus.Error.NoReply"));
+    }
+
+    #[tokio::test]
+    async fn position_stream_starts_from_current_position() {
+        use futures::StreamExt;
+
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), position: 42_000_000, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        let stream = Arc::new(player).subscribe_position().await.unwrap();
+        futures::pin_mut!(stream);
+
+        let first = tokio::time::timeout(Duration::from_millis(500), stream.next()).await.unwrap();
+        assert_eq!(first.unwrap().value, Duration::from_secs(42));
+    }
+
+    #[test]
+    fn zero_length_is_unknown() {
+        let mut map = mock::metadata_with_trackid("/track/1");
+        map.insert("mpris:length".to_string(), OwnedValue::from(0i64));
+        assert_eq!(Metadata::from(map.clone()).length, None);
+
+        map.insert("mpris:length".to_string(), OwnedValue::from(1_500_000i64));
+        assert_eq!(Metadata::from(map).length, Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn trackid_path() {
+        let metadata = Metadata::from(mock::metadata_with_trackid("/track/1"));
+        assert_eq!(metadata.trackid_path().unwrap().as_str(), "/track/1");
+
+        let map = HashMap::from([("mpris:trackid".to_string(), OwnedValue::try_from(Value::from("spotify:track:1")).unwrap())]);
+        let metadata = Metadata::from(map);
+        assert_eq!(metadata.trackid.as_deref(), Some("spotify:track:1"));
+        assert_eq!(metadata.trackid_path(), None);
+    }
+
+    #[test]
+    fn integer_widths() {
+        let widths = [
+            OwnedValue::from(128i16),
+            OwnedValue::from(128i32),
+            OwnedValue::from(128u32),
+            OwnedValue::from(128i64),
+            OwnedValue::from(128u64),
+        ];
+
+        for value in widths {
+            let mut map = mock::metadata_with_trackid("/track/1");
+            map.insert("xesam:audioBPM".to_string(), value.try_clone().unwrap());
+            map.insert("xesam:discNumber".to_string(), value.try_clone().unwrap());
+            map.insert("xesam:trackNumber".to_string(), value);
+
+            let metadata = Metadata::from(map);
+            assert_eq!((metadata.bpm, metadata.disc_number, metadata.track_number), (128, 128, 128));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn metadata_serde() {
+        let mut map = mock::metadata_with_trackid("/track/1");
+        map.insert("mpris:length".to_string(), OwnedValue::from(200_000_000i64));
+        map.insert("xesam:title".to_string(), Value::from("Title").try_into().unwrap());
+        map.insert("xesam:artist".to_string(), Value::from(vec!["A", "B"]).try_into().unwrap());
+        map.insert("xesam:contentCreated".to_string(), Value::from("2007-04-12").try_into().unwrap());
+        let metadata = Metadata::from(map);
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["length"], 200_000_000);
+        assert_eq!(value["created"], "2007-04-12");
+
+        // The raw values keep their value, but JSON doesn't keep the exact integer types, so they still parse the same
+        let deserialized: Metadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(Metadata { raw: metadata.raw.clone(), ..deserialized.clone() }, metadata);
+        assert_eq!(Metadata { raw: metadata.raw.clone(), ..Metadata::from(deserialized.raw) }, metadata);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn descriptor_serde() {
+        let descriptor = PlayerDescriptor {
+            bus_name: String::from("org.mpris.MediaPlayer2.mock"),
+            unique_name: None,
+            identity: String::from("Mock"),
+            desktop_entry: String::new(),
+            can_control: true
+        };
+
+        let value = serde_json::to_value(&descriptor).unwrap();
+        assert_eq!(value["bus_name"], "org.mpris.MediaPlayer2.mock");
+        assert_eq!(value["unique_name"], serde_json::Value::Null);
+        assert_eq!(value["can_control"], true);
+    }
+
+    #[cfg(feature = "art")]
+    #[tokio::test]
+    async fn load_art() {
+        let mut metadata = Metadata::from(mock::metadata_with_trackid("/track/1"));
+        assert!(matches!(metadata.load_art().await, Err(crate::ArtError::NoArt)));
+
+        metadata.art_url = Some(String::from("data:image/png;base64,iVBORw0K"));
+        assert_eq!(metadata.load_art().await.unwrap(), b"\x89PNG\r\n");
+        metadata.art_url = Some(String::from("data:text/plain,a%20b"));
+        assert_eq!(metadata.load_art().await.unwrap(), b"a b");
+
+        let path = std::env::temp_dir().join(format!("mpris art {}.png", std::process::id()));
+        std::fs::write(&path, b"art").unwrap();
+        metadata.art_url = Some(format!("file://{}", path.display()).replace(' ', "%20"));
+        assert_eq!(metadata.load_art().await.unwrap(), b"art");
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(metadata.load_art().await, Err(crate::ArtError::Io(_))));
+
+        metadata.art_url = Some(String::from("ftp://example.com/art.png"));
+        assert!(matches!(metadata.load_art().await, Err(crate::ArtError::UnsupportedScheme(scheme)) if scheme == "ftp"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn date_fields() {
+        let mut metadata = Metadata::from(mock::metadata_with_trackid("/track/1"));
+        metadata.created = String::from("2007-04-12T10:00:00+02:00");
+        metadata.first_used = String::from("2007-04-12T10:00:00");
+        metadata.last_used = String::from("2007-04-12");
+
+        assert_eq!(metadata.created_at().unwrap().to_rfc3339(), "2007-04-12T10:00:00+02:00");
+        assert_eq!(metadata.first_used_at().unwrap().to_rfc3339(), "2007-04-12T10:00:00+00:00");
+        assert_eq!(metadata.last_used_at().unwrap().to_rfc3339(), "2007-04-12T00:00:00+00:00");
+
+        metadata.created = String::from("2007-04-12 10:00:00+0200");
+        metadata.first_used = String::from("2007-04-12 10:00:00.5");
+        metadata.last_used = String::from(" 2007-04-12T10:00:00Z ");
+
+        assert_eq!(metadata.created_at().unwrap().to_rfc3339(), "2007-04-12T10:00:00+02:00");
+        assert_eq!(metadata.first_used_at().unwrap().to_rfc3339(), "2007-04-12T10:00:00.500+00:00");
+        assert_eq!(metadata.last_used_at().unwrap().to_rfc3339(), "2007-04-12T10:00:00+00:00");
+
+        for unparseable in ["last tuesday", "", "2007-04-12T", "2007-13-01", "2007-04-12 25:00:00"] {
+            metadata.created = String::from(unparseable);
+            assert_eq!(metadata.created_at(), None, "{unparseable}");
+        }
+    }
+
+    #[test]
+    fn micros_conversion() {
+        use micros::micros_to_duration;
+
+        assert_eq!(duration_to_micros(Duration::from_millis(1500)), 1_500_000);
+        assert_eq!(duration_to_micros(Duration::MAX), i64::MAX);
+        assert_eq!(micros_to_duration(1_500_000), Duration::from_millis(1500));
+        assert_eq!(micros_to_duration(-1), Duration::ZERO);
+        assert_eq!(micros_to_duration(i64::MIN), Duration::ZERO);
+    }
+
+    #[test]
+    fn tag_map() {
+        let mut metadata = Metadata::from(mock::metadata_with_trackid("/track/1"));
+        metadata.title = String::from("Title");
+        metadata.artists = vec![String::from("A"), String::from("B")];
+        metadata.track_number = 3;
+        metadata.created = String::from("2007-04-12T10:00:00Z");
+
+        let tags = metadata.to_tag_map();
+        assert_eq!(tags.get("TITLE").unwrap(), "Title");
+        assert_eq!(tags.get("ARTIST").unwrap(), "A; B");
+        assert_eq!(tags.get("TRACKNUMBER").unwrap(), "3");
+        assert_eq!(tags.get("DATE").unwrap(), "2007-04-12");
+        assert!(!tags.contains_key("ALBUM"));
+        assert!(!tags.contains_key("DISCNUMBER"));
+    }
+
+    #[tokio::test]
+    async fn shared_metadata_stream_feeds_every_clone() {
+        use futures::StreamExt;
+
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        let mut first = Arc::new(player).shared_metadata_stream().await.unwrap();
+        let mut second = first.clone();
+
+        assert_eq!(first.next().await.unwrap().value.trackid.as_deref(), Some("/track/1"));
+        assert_eq!(second.next().await.unwrap().value.trackid.as_deref(), Some("/track/1"));
+    }
+
+    #[tokio::test]
+    async fn dropped_subscriptions_release_match_rules() {
+        let bus = mock::PrivateBus::start();
+        let (player, _server) = bus.connect(mock::MockPlayer::default()).await;
+        let player = Arc::new(player);
+
+        let baseline = bus.match_rules(&player.connection).await;
+
+        for _ in 0..20 {
+            let seeked = player.clone().subscribe(Seeked).await.unwrap();
+            let playback = player.clone().subscribe_property_change(PlaybackStatus).await.unwrap();
+            seeked.unsubscribe().await;
+            drop(playback);
+
+            drop(player.clone().subscribe(Seeked).await.unwrap());
+        }
+
+        // Plain drops are removed in the background
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(bus.match_rules(&player.connection).await, baseline);
+    }
+
+    #[tokio::test]
+    async fn malformed_signal_is_skipped() {
+        let (player, server) = mock::connect(mock::MockPlayer::default()).await;
+        let seeked = Arc::new(player).subscribe(Seeked).await.unwrap();
+        futures::pin_mut!(seeked);
+
+        // A string instead of the position
+        server.emit_signal(None::<&str>, "/org/mpris/MediaPlayer2", "org.mpris.MediaPlayer2.Player", "Seeked", &("soon",)).await.unwrap();
+        mock::emit_seeked(&server, 42_000_000).await;
+
+        let signal = tokio::time::timeout(Duration::from_secs(1), seeked.next()).await.unwrap().unwrap();
+        assert_eq!(signal.value, Duration::from_secs(42));
+    }
+
+    #[tokio::test]
+    async fn next_seek() {
+        let (player, server) = mock::connect(mock::MockPlayer::default()).await;
+
+        let (position, _) = tokio::join!(
+            Arc::new(player).next_seek(),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                mock::emit_seeked(&server, 42_000_000).await;
+            }
+        );
+        assert_eq!(position.unwrap(), Duration::from_secs(42));
+    }
+
+    #[tokio::test]
+    async fn subscribe_all_signals() {
+        let bus = mock::PrivateBus::start();
+        let (player, server) = bus.connect(mock::MockPlayer::default()).await;
+        // Another player on the same path, which's signals must not leak in
+        let (_other, other_server) = bus.connect_as("org.mpris.MediaPlayer2.other", mock::MockPlayer::default()).await;
+        let player = Arc::new(player);
+
+        let baseline = bus.match_rules(&player.connection).await;
+        let signals = player.clone().subscribe_all_signals().await.unwrap();
+        futures::pin_mut!(signals);
+        assert_eq!(bus.match_rules(&player.connection).await, baseline + 1);
+
+        mock::emit_seeked(&other_server, 1_000_000).await;
+        mock::emit_seeked(&server, 42_000_000).await;
+
+        let signal = tokio::time::timeout(Duration::from_secs(1), signals.next()).await.unwrap().unwrap();
+        assert!(matches!(signal.value, signals::AnySignal::Seeked(position) if position == Duration::from_secs(42)));
+
+        // Signals with several arguments, and with a struct
+        let mock = mock::MockPlayer { tracks: Some(Vec::new()), playlists: Some(Vec::new()), ..Default::default() };
+        let (player, server) = bus.connect_as("org.mpris.MediaPlayer2.lists", mock).await;
+        let player = Arc::new(player);
+        let signals = player.clone().subscribe_all_signals().await.unwrap();
+        futures::pin_mut!(signals);
+
+        player.add_track("file:///new.mp3", NO_TRACK, false).await.unwrap();
+        let signal = tokio::time::timeout(Duration::from_secs(1), signals.next()).await.unwrap().unwrap();
+        assert!(matches!(signal.value, signals::AnySignal::TrackAdded(metadata, after) if metadata.trackid.as_deref() == Some("/track/added") && after == NO_TRACK));
+
+        let iface = server.object_server().interface::<_, mock::MockPlaylists>("/org/mpris/MediaPlayer2").await.unwrap();
+        mock::MockPlaylists::playlist_changed(iface.signal_emitter(), (ObjectPath::from_static_str_unchecked("/list/a"), "A", "")).await.unwrap();
+        let signal = tokio::time::timeout(Duration::from_secs(1), signals.next()).await.unwrap().unwrap();
+        assert!(matches!(signal.value, signals::AnySignal::PlaylistChanged(playlist) if playlist.name == "A"));
+    }
+
+    #[tokio::test]
+    async fn events_unsubscribe() {
+        let bus = mock::PrivateBus::start();
+        let (player, _server) = bus.connect(mock::MockPlayer::default()).await;
+        let player = Arc::new(player);
+
+        let baseline = bus.match_rules(&player.connection).await;
+
+        for _ in 0..10 {
+            let events = player.clone().events().await.unwrap();
+            assert!(bus.match_rules(&player.connection).await > baseline);
+
+            events.unsubscribe().await;
+            assert_eq!(bus.match_rules(&player.connection).await, baseline);
+        }
+    }
+
+    #[tokio::test]
+    async fn playback_controls() {
+        let mock = mock::MockPlayer::default();
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.play().await.unwrap();
+        assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Playing);
+        player.pause().await.unwrap();
+        assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Paused);
+        player.play_pause().await.unwrap();
+        assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Playing);
+        player.stop().await.unwrap();
+        assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Stopped);
+
+        assert_eq!(*calls.lock().unwrap(), vec!["Play", "Pause", "PlayPause", "Stop"]);
+
+        // The player isn't called at all without the capability
+        let mock = mock::MockPlayer { can_play: false, can_pause: false, can_control: false, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        for result in [player.play().await, player.pause().await, player.play_pause().await, player.stop().await] {
+            assert!(matches!(result, Err(Error::NotControllable { .. })));
+        }
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn track_navigation() {
+        let mock = mock::MockPlayer::default();
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.next().await.unwrap();
+        player.previous().await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Next", "Previous"]);
+
+        // At the end of the tracklist
+        let mock = mock::MockPlayer { can_go_next: false, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(matches!(player.next().await, Err(Error::NotControllable { capability: "CanGoNext" })));
+        player.previous().await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Previous"]);
+    }
+
+    #[tokio::test]
+    async fn seek() {
+        let mock = mock::MockPlayer::default();
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.seek(Duration::from_millis(1500), false).await.unwrap();
+        player.seek(Duration::from_secs(2), true).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Seek(1500000)", "Seek(-2000000)"]);
+
+        let mock = mock::MockPlayer { can_seek: false, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(matches!(player.seek(Duration::from_secs(1), false).await, Err(Error::NotControllable { capability: "CanSeek" })));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_position_out_of_range() {
+        let mut metadata = mock::metadata_with_trackid("/track/1");
+        metadata.insert("mpris:length".to_string(), OwnedValue::from(60_000_000i64));
+        let mock = mock::MockPlayer { metadata, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        let metadata = player.get(properties::Metadata).await.unwrap();
+        player.set_position(metadata.trackid_path().unwrap().as_str(), Duration::from_secs(120)).await.unwrap();
+        assert!(calls.lock().unwrap().is_empty());
+
+        player.set_position("/track/1", Duration::from_secs(60)).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["SetPosition(/track/1, 60000000)"]);
+    }
+
+    #[tokio::test]
+    async fn uri_schemes() {
+        let mock = mock::MockPlayer { supported_uri_schemes: vec![String::from("file"), String::from("HTTPS"), String::from("spotify")], ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        let schemes = player.supported_uri_schemes().await.unwrap();
+        assert_eq!(schemes, vec![UriScheme::File, UriScheme::Https, UriScheme::Other(String::from("spotify"))]);
+
+        assert!(UriScheme::File.matches("file:///music/song.mp3"));
+        assert!(UriScheme::Https.matches("HTTPS://example.com"));
+        assert!(!UriScheme::Http.matches("https://example.com"));
+        assert!(schemes[2].matches("spotify:track:1"));
+        assert!(!UriScheme::File.matches("song.mp3"));
+        assert_eq!(UriScheme::from(" rtsp:// "), UriScheme::Rtsp);
+    }
+
+    #[tokio::test]
+    async fn open_uri() {
+        let mock = mock::MockPlayer { supported_uri_schemes: vec![String::from("file")], ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.open_uri("FILE:///music/song.mp3").await.unwrap();
+        assert!(matches!(player.open_uri("https://example.com/stream").await, Err(Error::UnsupportedUri(_))));
+        assert!(matches!(player.open_uri(String::from("song.mp3")).await, Err(Error::UnsupportedUri(_))));
+        assert_eq!(*calls.lock().unwrap(), vec!["OpenUri(FILE:///music/song.mp3)"]);
+
+        // Nothing listed, so it's up to the player
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+        player.open_uri("https://example.com/stream").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn tracklist() {
+        let mut second = mock::metadata_with_trackid("/track/2");
+        second.insert("xesam:title".to_string(), OwnedValue::from(zbus::zvariant::Str::from("Second")));
+        let mock = mock::MockPlayer { tracks: Some(vec![mock::metadata_with_trackid("/track/1"), second]), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(player.interfaces().await.unwrap().contains(&Interface::TrackList));
+
+        let tracks = player.get(properties::Tracks).await.unwrap();
+        assert_eq!(tracks, vec!["/track/1", "/track/2"]);
+
+        // Unknown tracks are left out
+        let metadata = player.get_tracks_metadata(&["/track/2", "/track/3"]).await.unwrap();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata[0].trackid.as_deref(), Some("/track/2"));
+        assert_eq!(metadata[0].title, "Second");
+        assert!(player.get_tracks_metadata(&["not a path"]).await.is_err());
+
+        player.add_track("file:///new.mp3", NO_TRACK, true).await.unwrap();
+        player.remove_track(&tracks[0]).await.unwrap();
+        player.go_to("/track/2").await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![
+            format!("AddTrack(file:///new.mp3, {NO_TRACK}, true)"),
+            String::from("RemoveTrack(/track/1)"),
+            String::from("GoTo(/track/2)")
+        ]);
+
+        let mock = mock::MockPlayer { tracks: Some(Vec::new()), can_edit_tracks: false, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert!(player.get(properties::Tracks).await.unwrap().is_empty());
+        assert!(matches!(player.remove_track("/track/1").await, Err(Error::NotControllable { capability: "CanEditTracks" })));
+    }
+
+    #[tokio::test]
+    async fn tracklist_signals() {
+        use signals::{TrackAdded, TrackListReplaced, TrackMetadataChanged, TrackRemoved};
+
+        let mock = mock::MockPlayer { tracks: Some(vec![mock::metadata_with_trackid("/track/1")]), ..Default::default() };
+        let (player, server) = mock::connect(mock).await;
+        let player = Arc::new(player);
+
+        let mut added = player.clone().subscribe(TrackAdded).await.unwrap();
+        let mut removed = player.clone().subscribe(TrackRemoved).await.unwrap();
+        let mut replaced = player.clone().subscribe(TrackListReplaced).await.unwrap();
+        let mut changed = player.clone().subscribe(TrackMetadataChanged).await.unwrap();
+
+        player.add_track("file:///new.mp3", "/track/1", false).await.unwrap();
+        let (metadata, after) = added.next().await.unwrap().value;
+        assert_eq!(metadata.trackid.as_deref(), Some("/track/added"));
+        assert_eq!(after, "/track/1");
+
+        player.remove_track("/track/1").await.unwrap();
+        assert_eq!(removed.next().await.unwrap().value, "/track/1");
+
+        let iface = server.object_server().interface::<_, mock::MockTrackList>("/org/mpris/MediaPlayer2").await.unwrap();
+        let tracks = vec![ObjectPath::from_static_str_unchecked("/track/3"), ObjectPath::from_static_str_unchecked("/track/4")];
+        mock::MockTrackList::track_list_replaced(iface.signal_emitter(), tracks, ObjectPath::from_static_str_unchecked("/track/4")).await.unwrap();
+        assert_eq!(replaced.next().await.unwrap().value, (vec![String::from("/track/3"), String::from("/track/4")], String::from("/track/4")));
+
+        let mut metadata = mock::metadata_with_trackid("/track/3");
+        metadata.insert("xesam:title".to_string(), OwnedValue::from(zbus::zvariant::Str::from("Renamed")));
+        mock::MockTrackList::track_metadata_changed(iface.signal_emitter(), ObjectPath::from_static_str_unchecked("/track/3"), metadata).await.unwrap();
+        let (track_id, metadata) = changed.next().await.unwrap().value;
+        assert_eq!(track_id, "/track/3");
+        assert_eq!(metadata.title, "Renamed");
+    }
+
+    #[tokio::test]
+    async fn playlists() {
+        let playlists = vec![(String::from("/list/b"), String::from("B")), (String::from("/list/a"), String::from("A"))];
+        let mock = mock::MockPlayer { playlists: Some(playlists), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, server) = mock::connect(mock).await;
+        let player = Arc::new(player);
+
+        assert!(player.interfaces().await.unwrap().contains(&Interface::Playlists));
+        assert_eq!(player.get(properties::PlaylistCount).await.unwrap(), 2);
+        // Orderings not in the specs are left out
+        assert_eq!(player.get(properties::Orderings).await.unwrap(), vec![PlaylistOrdering::Alphabetical]);
+        assert_eq!(player.get(properties::ActivePlaylist).await.unwrap(), None);
+
+        let playlists = player.get_playlists(0, 10, PlaylistOrdering::Alphabetical, true).await.unwrap();
+        assert_eq!(playlists.iter().map(|playlist| playlist.name.as_str()).collect::<Vec<_>>(), vec!["B", "A"]);
+        assert_eq!(player.get_playlists(1, 10, PlaylistOrdering::Alphabetical, false).await.unwrap().len(), 1);
+
+        player.activate_playlist(&playlists[1].id).await.unwrap();
+        assert_eq!(player.get(properties::ActivePlaylist).await.unwrap(), Some(playlists[1].clone()));
+        assert_eq!(*calls.lock().unwrap(), vec![
+            "GetPlaylists(0, 10, Alphabetical, true)",
+            "GetPlaylists(1, 10, Alphabetical, false)",
+            "ActivatePlaylist(/list/a)"
+        ]);
+
+        let mut changed = player.clone().subscribe(signals::PlaylistChanged).await.unwrap();
+        let iface = server.object_server().interface::<_, mock::MockPlaylists>("/org/mpris/MediaPlayer2").await.unwrap();
+        mock::MockPlaylists::playlist_changed(iface.signal_emitter(), (ObjectPath::from_static_str_unchecked("/list/a"), "Renamed", "file:///icon.png")).await.unwrap();
+
+        let playlist = changed.next().await.unwrap().value;
+        assert_eq!(playlist.id.as_str(), "/list/a");
+        assert_eq!(playlist.name, "Renamed");
+        assert_eq!(playlist.icon, "file:///icon.png");
+    }
+
+    #[test]
+    fn playlist_ordering_names() {
+        assert_eq!(PlaylistOrdering::from_name("CreationDate"), Some(PlaylistOrdering::Created));
+        assert_eq!(PlaylistOrdering::from_name(" lastplaydate "), Some(PlaylistOrdering::Played));
+        assert_eq!(PlaylistOrdering::from_name("Created"), None);
+        assert_eq!(PlaylistOrdering::Modified.as_str(), "ModifiedDate");
+    }
+
+    #[tokio::test]
+    async fn set_loop_verified() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+        assert!(player.set_loop_verified(Loop::Playlist, Duration::from_secs(1)).await.unwrap());
+        assert_eq!(player.get(properties::LoopStatus).await.unwrap(), Loop::Playlist);
+
+        let mock = mock::MockPlayer { ignore_writes: true, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert!(!player.set_loop_verified(Loop::Track, Duration::from_millis(200)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn set_fullscreen_checks_capability() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+        assert!(matches!(player.set_fullscreen(true).await, Err(Error::NotControllable { capability: "CanSetFullscreen" })));
+
+        let (player, _server) = mock::connect(mock::MockPlayer { can_set_fullscreen: true, ..Default::default() }).await;
+        assert!(player.set_fullscreen_verified(true, Duration::from_secs(1)).await.unwrap());
+
+        player.toggle_fullscreen().await.unwrap();
+        assert!(!player.get(properties::Fullscreen).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn now_playing_stream() {
+        let mut metadata = mock::metadata_with_trackid("/track/1");
+        metadata.insert("xesam:title".to_string(), OwnedValue::from(zbus::zvariant::Str::from("Title")));
+        metadata.insert("mpris:length".to_string(), OwnedValue::from(240_000_000i64));
+
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), position: 83_000_000, metadata, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        let now_playing = Arc::new(player).subscribe_now_playing().await.unwrap();
+        futures::pin_mut!(now_playing);
+
+        let first = tokio::time::timeout(Duration::from_secs(1), now_playing.next()).await.unwrap().unwrap().value;
+        assert_eq!(first.metadata.title, "Title");
+        assert_eq!(first.position, Duration::from_secs(83));
+        assert_eq!(first.length, Some(Duration::from_secs(240)));
+        assert_eq!(first.playback, Playback::Paused);
+    }
+
+    #[tokio::test]
+    async fn owner_pid() {
+        let bus = mock::PrivateBus::start();
+        let (player, _server) = bus.connect(mock::MockPlayer::default()).await;
+
+        assert_eq!(player.owner_pid().await.unwrap(), std::process::id());
+    }
+
+    #[tokio::test]
+    async fn is_writable() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+
+        assert!(player.is_writable(properties::LoopStatus).await);
+        assert!(!player.is_writable(PlaybackStatus).await);
+        assert!(!player.is_writable(properties::Shuffle).await);
+    }
+
+    #[tokio::test]
+    async fn interfaces() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+
+        let interfaces = player.interfaces().await.unwrap();
+        assert_eq!(interfaces, HashSet::from([Interface::MediaPlayer2, Interface::Player]));
+    }
+
+    #[tokio::test]
+    async fn supports_scheme() {
+        let mock = mock::MockPlayer { supported_uri_schemes: vec![String::from("file"), String::from("HTTPS")], ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(player.supports_scheme("file").await);
+        assert!(player.supports_scheme("https://").await);
+        assert!(!player.supports_scheme("rtsp").await);
+    }
+
+    #[tokio::test]
+    async fn position_heartbeat() {
+        use streams::PositionTick;
+
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), position: 83_000_000, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        let heartbeat = Arc::new(player).subscribe_position().await.unwrap().with_heartbeat(Duration::from_millis(100));
+        futures::pin_mut!(heartbeat);
+
+        let first = tokio::time::timeout(Duration::from_millis(50), heartbeat.next()).await.unwrap().unwrap();
+        assert_eq!(first.value, PositionTick::Changed(Duration::from_secs(83)));
+
+        let second = tokio::time::timeout(Duration::from_millis(500), heartbeat.next()).await.unwrap().unwrap();
+        assert_eq!(second.value, PositionTick::Heartbeat(Duration::from_secs(83)));
+    }
+
+    #[tokio::test]
+    async fn descriptor() {
+        let bus = mock::PrivateBus::start();
+        let (player, server) = bus.connect(mock::MockPlayer { can_control: false, ..Default::default() }).await;
+
+        let descriptor = player.descriptor().await;
+        assert_eq!(descriptor.bus_name, "org.mpris.MediaPlayer2.mock");
+        assert_eq!(descriptor.unique_name.as_deref(), server.unique_name().map(|name| name.as_str()));
+        assert!(!descriptor.can_control);
+    }
+
+    #[tokio::test]
+    async fn nested_variants() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+
+        assert!(player.get(properties::CanQuit).await.unwrap());
+
+        let all = player.get_all(Interface::MediaPlayer2).await.unwrap();
+        assert!(all.get("CanQuit").unwrap().downcast_ref::<bool>().unwrap());
+        assert_eq!(player.properties().await.can_quit, Some(true));
+
+        let stream = Arc::new(player).subscribe_property_change(properties::CanQuit).await.unwrap();
+        futures::pin_mut!(stream);
+        assert!(stream.next().await.unwrap().value);
+    }
+
+    #[test]
+    fn value_coercion() {
+        use zbus::zvariant::Str;
+
+        let nested = |value: Value<'static>| OwnedValue::try_from(Value::Value(Box::new(Value::Value(Box::new(value))))).unwrap();
+
+        // Nested variants
+        let value = value::unwrap_variant(nested(Value::from(true)));
+        assert!(value.downcast_ref::<bool>().unwrap());
+        assert_eq!(value::as_string(&nested(Value::from("title"))).as_deref(), Some("title"));
+
+        // Strings and object paths
+        assert_eq!(value::as_string(&OwnedValue::from(Str::from("title"))).as_deref(), Some("title"));
+        assert_eq!(value::as_string(&OwnedValue::from(ObjectPath::from_static_str_unchecked("/track/1"))).as_deref(), Some("/track/1"));
+        assert_eq!(value::as_string(&OwnedValue::from(1i32)), None);
+
+        // A single string as a list
+        let list = OwnedValue::try_from(Value::from(vec!["a", "b"])).unwrap();
+        assert_eq!(value::as_string_vec(&list), Some(vec![String::from("a"), String::from("b")]));
+        assert_eq!(value::as_string_vec(&OwnedValue::from(Str::from("a"))), Some(vec![String::from("a")]));
+        assert_eq!(value::as_string_vec(&nested(Value::from("a"))), Some(vec![String::from("a")]));
+
+        // Every integer width
+        assert_eq!(value::as_i64(&OwnedValue::from(7u8)), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(7i16)), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(7u16)), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(-7i32)), Some(-7));
+        assert_eq!(value::as_i64(&OwnedValue::from(7u32)), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(-7i64)), Some(-7));
+        assert_eq!(value::as_i64(&OwnedValue::from(7u64)), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(u64::MAX)), None);
+        assert_eq!(value::as_i64(&nested(Value::from(7u32))), Some(7));
+        assert_eq!(value::as_i64(&OwnedValue::from(Str::from("7"))), None);
+
+        // Integers as floats
+        assert_eq!(value::as_f64(&OwnedValue::from(0.5f64)), Some(0.5));
+        assert_eq!(value::as_f64(&OwnedValue::from(1i32)), Some(1.0));
+        assert_eq!(value::as_f64(&nested(Value::from(0.25f64))), Some(0.25));
+        assert_eq!(value::as_f64(&OwnedValue::from(Str::from("0.5"))), None);
+
+        // Parsing into the type of a property
+        assert_eq!(value::parse::<i64>(OwnedValue::from(7u64)), Some(7));
+        assert_eq!(value::parse::<f64>(nested(Value::from(2i32))), Some(2.0));
+        assert_eq!(value::parse::<String>(OwnedValue::from(ObjectPath::from_static_str_unchecked("/track/1"))).as_deref(), Some("/track/1"));
+        assert_eq!(value::parse::<Vec<String>>(OwnedValue::from(Str::from("a"))), Some(vec![String::from("a")]));
+        assert_eq!(value::parse::<bool>(OwnedValue::from(1i32)), None);
+        assert_eq!(value::parse::<i64>(OwnedValue::from(u64::MAX)), None);
+    }
+
+    #[tokio::test]
+    async fn cancelled_reads() {
+        let (player, _server) = mock::connect(mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() }).await;
+
+        // Polled once, then dropped while the calls are in flight
+        {
+            let properties = std::pin::pin!(player.properties());
+            assert!(futures::poll!(properties).is_pending());
+        }
+        {
+            let state = std::pin::pin!(player.capture_state());
+            assert!(futures::poll!(state).is_pending());
+        }
+
+        let properties = player.properties().await;
+        assert_eq!(properties.playback_status, Some(Playback::Playing));
+        assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Playing);
+    }
+
+    #[tokio::test]
+    async fn property_cache() {
+        let (player, server) = mock::connect(mock::MockPlayer::default()).await;
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        player.enable_property_cache().await.unwrap();
+        assert_eq!(player.get(properties::Volume).await.unwrap(), 1.0);
+
+        // Not reported, so the cached value is served
+        iface.get_mut().await.volume = 0.2;
+        assert_eq!(player.get(properties::Volume).await.unwrap(), 1.0);
+
+        let until = async |volume: f64| tokio::time::timeout(Duration::from_secs(1), async {
+            while player.get(properties::Volume).await.unwrap() != volume {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+
+        // Reported with the value
+        iface.get_mut().await.volume = 0.5;
+        iface.get().await.volume_changed(iface.signal_emitter()).await.unwrap();
+        until(0.5).await;
+
+        // Reported without the value, so it's read again
+        iface.get_mut().await.volume = 0.7;
+        let invalidated: &[&str] = &["Volume"];
+        zbus::fdo::Properties::properties_changed(iface.signal_emitter(), Interface::Player.as_str().try_into().unwrap(), HashMap::new(), invalidated.into()).await.unwrap();
+        until(0.7).await;
+
+        // The position is never cached
+        iface.get_mut().await.position = 5_000_000;
+        assert_eq!(player.get(Position).await.unwrap(), Duration::from_secs(5));
+
+        player.disable_property_cache();
+        iface.get_mut().await.volume = 0.1;
+        assert_eq!(player.get(properties::Volume).await.unwrap(), 0.1);
+    }
+
+    #[tokio::test]
+    async fn properties_preserve_absence() {
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), rate: 1.5, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        let properties = player.properties().await;
+        assert_eq!(properties.can_quit, Some(true));
+        assert_eq!(properties.playback_status, Some(Playback::Playing));
+        assert_eq!(properties.rate, Some(1.5));
+        assert_eq!(properties.loop_status, Some(Loop::None));
+        assert_eq!(properties.shuffle, None);
+        assert_eq!(properties.identity, None);
+        assert_eq!(properties.tracks, None);
+        assert_eq!(properties.playlist_count, None);
+
+        let mock = mock::MockPlayer { tracks: Some(vec![mock::metadata_with_trackid("/track/1")]), playlists: Some(vec![(String::from("/list/a"), String::from("A"))]), ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        let properties = player.properties().await;
+        assert_eq!(properties.tracks, Some(vec![String::from("/track/1")]));
+        assert_eq!(properties.can_edit_tracks, Some(true));
+        assert_eq!(properties.playlist_count, Some(1));
+        assert_eq!(properties.active_playlist, Some(None));
+    }
+
+    #[tokio::test]
+    async fn seek_right_after_subscribe() {
+        let (player, server) = mock::connect(mock::MockPlayer { playback_status: String::from("Paused"), ..Default::default() }).await;
+        let position = Arc::new(player).subscribe_position().await.unwrap();
+        futures::pin_mut!(position);
+
+        mock::emit_seeked(&server, 42_000_000).await;
+
+        // The seek may be yielded before, or after the seeded position
+        let reflected = position.any(|tick| futures::future::ready(tick.value == Duration::from_secs(42)));
+        assert!(tokio::time::timeout(Duration::from_millis(500), reflected).await.unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_estimation_is_exact() {
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), rate: 2.0, position: 10_000_000, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        let position = Arc::new(player).subscribe_position().await.unwrap();
+        futures::pin_mut!(position);
+
+        // Skip the seed (which may come more than once, as the initial states arrive), then a tick of 1 second at 2x
+        let start = tokio::time::Instant::now();
+        let mut ticks = position.map(|tick| tick.value).skip_while(|value| futures::future::ready(*value == Duration::from_secs(10)));
+        assert_eq!(ticks.next().await.unwrap(), Duration::from_secs(12));
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+        assert_eq!(ticks.next().await.unwrap(), Duration::from_secs(14));
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_resync() {
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), position: 10_000_000, ..Default::default() };
+        let (player, server) = mock::connect(mock).await;
+        let position = Arc::new(player).subscribe_position().await.unwrap().with_resync(Duration::from_secs(5));
+        futures::pin_mut!(position);
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(position.next().await.unwrap().value, Duration::from_secs(10));
+
+        // The player jumps without emitting Seeked, so only the resync notices it
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.position = 60_000_000;
+
+        let mut ticks = position.map(|tick| tick.value).skip_while(|value| futures::future::ready(*value == Duration::from_secs(10)));
+        assert_eq!(ticks.next().await.unwrap(), Duration::from_secs(60));
+
+        // Paused time skips ahead while the read is in flight, so only the first resync is pinned down
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_secs(5) && elapsed < Duration::from_secs(10), "{elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn get_is_never_stale() {
+        let (player, server) = mock::connect(mock::MockPlayer::default()).await;
+        assert_eq!(player.get(Position).await.unwrap(), Duration::ZERO);
+
+        // Position changes are never reported, so a cached value would stay zero
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.position = 5_000_000;
+
+        assert_eq!(player.get(Position).await.unwrap(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn malformed_metadata() {
+        let (player, server) = mock::connect(mock::MockPlayer { malformed_metadata: true, ..Default::default() }).await;
+        let player = Arc::new(player);
+
+        match player.get(properties::Metadata).await {
+            Err(Error::ParseFailed { property, signature }) => assert_eq!((property, signature.as_str()), ("Metadata", "as")),
+            other => panic!("Expected a malformed metadata error, got {other:?}")
+        }
+
+        let metadata = player.clone().subscribe_property_change(properties::Metadata).await.unwrap();
+        futures::pin_mut!(metadata);
+
+        // The current, malformed value is skipped, and the stream goes on
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+        {
+            let mut mock = iface.get_mut().await;
+            mock.malformed_metadata = false;
+            mock.metadata = mock::metadata_with_trackid("/track/2");
+        }
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(1), metadata.next()).await.unwrap().unwrap();
+        assert_eq!(changed.value.trackid.as_deref(), Some("/track/2"));
+        assert_eq!(player.get(properties::Metadata).await.unwrap().trackid.as_deref(), Some("/track/2"));
+    }
+
+    #[tokio::test]
+    async fn art_url_changes_only() {
+        let (player, server) = mock::connect(mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() }).await;
+        let art = Arc::new(player).subscribe_art_url().await.unwrap();
+        futures::pin_mut!(art);
+
+        assert_eq!(art.next().await.unwrap().value, None);
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        let mut metadata = mock::metadata_with_trackid("/track/2");
+        iface.get_mut().await.metadata = metadata.clone();
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+
+        metadata.insert("mpris:artUrl".to_string(), OwnedValue::from(zbus::zvariant::Str::from("file:///art.png")));
+        iface.get_mut().await.metadata = metadata;
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+
+        // The track change without art is skipped
+        let changed = tokio::time::timeout(Duration::from_secs(1), art.next()).await.unwrap().unwrap();
+        assert_eq!(changed.value.as_deref(), Some("file:///art.png"));
+    }
+
+    #[tokio::test]
+    async fn set_and_set_controlled() {
+        fn writable<P: WritableProperty>(_: P) {}
+        fn control_writable<P: ControlWritableProperty>(_: P) {}
+
+        writable(properties::Fullscreen);
+        control_writable(properties::Volume);
+        control_writable(properties::LoopStatus);
+        control_writable(Rate);
+        control_writable(properties::Shuffle);
+
+        let mock = mock::MockPlayer { can_control: false, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.set(properties::Fullscreen, true).await.unwrap();
+        assert!(matches!(player.set_controlled(properties::Volume, 0.5).await, Err(Error::NotControllable { capability: "CanControl" })));
+        assert_eq!(*calls.lock().unwrap(), vec!["Fullscreen = true"]);
+    }
+
+    #[tokio::test]
+    async fn shuffle_state() {
+        // The mock doesn't have Shuffle, like most browsers
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+
+        assert!(player.get(properties::Shuffle).await.is_err());
+        assert_eq!(player.shuffle_state().await, None);
+    }
+
+    #[tokio::test]
+    async fn apply() {
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.apply(&[
+            PlayerAction::OpenUri(String::from("file:///song.mp3")),
+            PlayerAction::SeekTo(Duration::from_secs(30)),
+            PlayerAction::SetVolume(0.5),
+            PlayerAction::Play,
+        ]).await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec!["OpenUri(file:///song.mp3)", "SetPosition(/track/opened, 30000000)", "Volume = 0.5", "Play"]);
+    }
+
+    #[tokio::test]
+    async fn apply_stops_at_failure() {
+        let mock = mock::MockPlayer { can_control: false, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(player.apply(&[PlayerAction::SetVolume(0.5), PlayerAction::Play]).await.is_err());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn restore_state() {
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        let state = player.capture_state().await;
+        assert_eq!(state, PlayerState { volume: Some(1.0), playback: Some(Playback::Playing), rate: Some(1.0) });
+
+        player.set_controlled(properties::Volume, 0.2).await.unwrap();
+        player.pause().await.unwrap();
+
+        player.restore_state(state).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Volume = 0.2", "Pause", "Volume = 1", "Play"]);
+
+        // Nothing changed since
+        player.restore_state(state).await.unwrap();
+        assert_eq!(calls.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn set_controlled() {
+        let mock = mock::MockPlayer::default();
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.set_controlled(properties::Volume, 0.5).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Volume = 0.5"]);
+    }
+
+    #[tokio::test]
+    async fn events() {
+        let (player, server) = mock::connect(mock::MockPlayer { volume: 0.5, ..Default::default() }).await;
+
+        let events = Arc::new(player).events().await.unwrap();
+        futures::pin_mut!(events);
+
+        mock::emit_seeked(&server, 42_000_000).await;
+
+        let mut updates = Vec::new();
+        while !updates.contains(&streams::PlayerUpdate::Seeked(Duration::from_secs(42))) {
+            let update = tokio::time::timeout(Duration::from_millis(500), events.next()).await.unwrap().unwrap();
+            updates.push(update.value);
+        }
+
+        assert!(updates.contains(&streams::PlayerUpdate::Playback(Playback::Stopped)));
+        assert!(updates.contains(&streams::PlayerUpdate::Volume(0.5)));
+        assert!(updates.contains(&streams::PlayerUpdate::Loop(Loop::None)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_calls() {
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), rate: 1.5, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+        let player = Arc::new(player);
+
+        let tasks = (0..64).map(|i| {
+            let player = player.clone();
+            tokio::spawn(async move {
+                assert_eq!(player.get(PlaybackStatus).await.unwrap(), Playback::Playing);
+                assert_eq!(player.get(Rate).await.unwrap(), 1.5);
+                player.seek(Duration::from_micros(i), false).await.unwrap();
+            })
+        });
+
+        let finished = tokio::time::timeout(Duration::from_secs(5), futures::future::join_all(tasks)).await.unwrap();
+        assert!(finished.into_iter().all(|task| task.is_ok()));
+        assert_eq!(calls.lock().unwrap().len(), 64);
+    }
+
+    #[tokio::test]
+    async fn loose_numbers() {
+        let mock = mock::MockPlayer { position: 42_000_000, rate: 2.0, loose_numbers: true, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        assert_eq!(player.get(Position).await.unwrap(), Duration::from_secs(42));
+        assert_eq!(player.get(Rate).await.unwrap(), 2.0);
+
+        let properties = player.properties().await;
+        assert_eq!(properties.position, Some(Duration::from_secs(42)));
+        assert_eq!(properties.rate, Some(2.0));
+
+        let rate = Arc::new(player).subscribe_property_change(Rate).await.unwrap();
+        futures::pin_mut!(rate);
+        assert_eq!(rate.next().await.unwrap().value, 2.0);
+    }
+
+    #[tokio::test]
+    async fn get_or_falls_back() {
+        let (player, _server) = mock::connect(mock::MockPlayer { rate: 2.0, ..Default::default() }).await;
+
+        assert_eq!(player.get_or(Rate, 1.0).await, 2.0);
+        assert_eq!(player.get_or(properties::Volume, 1.0).await, 1.0);
+    }
+
+    #[tokio::test]
+    async fn playback_stream_reports_player_gone() {
+        let bus = mock::PrivateBus::start();
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), ..Default::default() };
+        let (player, server) = bus.connect(mock).await;
+        let mut playback = Arc::new(player).subscribe_playback().await.unwrap();
+
+        assert_eq!(playback.next().await.unwrap().value, streams::PlaybackEvent::Status(Playback::Paused));
+
+        server.close().await.unwrap();
+        let gone = tokio::time::timeout(Duration::from_secs(5), playback.next()).await.unwrap();
+        assert_eq!(gone.unwrap().value, streams::PlaybackEvent::PlayerGone);
+        assert!(playback.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn seek_to_uses_current_trackid() {
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.seek_to(Duration::from_secs(90)).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["SetPosition(/track/1, 90000000)"]);
+    }
+
+    #[tokio::test]
+    async fn seek_to_without_trackid_seeks_relative() {
+        let mock = mock::MockPlayer { position: 30_000_000, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        player.seek_to(Duration::from_secs(10)).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Seek(-20000000)"]);
+    }
+
+    #[tokio::test]
+    async fn position_fraction() {
+        let mut metadata = mock::metadata_with_trackid("/track/1");
+        metadata.insert("mpris:length".to_string(), OwnedValue::from(200_000_000i64));
+        let mock = mock::MockPlayer { metadata, position: 50_000_000, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert_eq!(player.position_fraction().await, Some(0.25));
+
+        player.seek_to_fraction(0.5).await.unwrap();
+        player.seek_to_fraction(2.0).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["SetPosition(/track/1, 100000000)", "SetPosition(/track/1, 200000000)"]);
+    }
+
+    #[tokio::test]
+    async fn supports_position() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+        assert!(!player.supports_position().await);
+
+        let metadata = HashMap::from([("mpris:length".to_string(), OwnedValue::from(200_000_000i64))]);
+        let (player, _server) = mock::connect(mock::MockPlayer { metadata, ..Default::default() }).await;
+        assert!(player.supports_position().await);
+    }
+
+    #[tokio::test]
+    async fn position_unsupported() {
+        let metadata = HashMap::from([("mpris:length".to_string(), OwnedValue::from(200_000_000i64))]);
+        let (player, _server) = mock::connect(mock::MockPlayer { metadata, position_unsupported: true, ..Default::default() }).await;
+
+        assert!(!player.supports_position().await);
+        assert_eq!(player.position_fraction().await, None);
+        assert!(Arc::new(player).subscribe_position().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn error_kinds() {
+        let bus = mock::PrivateBus::start();
+        let (player, server) = bus.connect(mock::MockPlayer::default()).await;
+
+        assert!(matches!(player.get(properties::Shuffle).await, Err(Error::UnsupportedProperty("Shuffle"))));
+        assert!(matches!(player.get(properties::Tracks).await, Err(Error::UnsupportedProperty("Tracks"))));
+
+        drop(server);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(matches!(player.get(properties::PlaybackStatus).await, Err(Error::PlayerGone)));
+        assert!(matches!(player.play().await, Err(Error::PlayerGone)));
+    }
+
+    #[tokio::test]
+    async fn time_remaining() {
+        let metadata = HashMap::from([("mpris:length".to_string(), OwnedValue::from(200_000_000i64))]);
+        let mock = mock::MockPlayer { metadata: metadata.clone(), position: 100_000_000, rate: 2.0, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert_eq!(player.time_remaining().await, Some(Duration::from_secs(50)));
+
+        let mock = mock::MockPlayer { metadata: metadata.clone(), position: 300_000_000, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert_eq!(player.time_remaining().await, Some(Duration::ZERO));
+
+        let mock = mock::MockPlayer { metadata: metadata.clone(), rate: 0.0, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert_eq!(player.time_remaining().await, None);
+
+        // The time left would overflow a Duration
+        let mock = mock::MockPlayer { metadata, rate: 1e-300, ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+        assert_eq!(player.time_remaining().await, None);
+
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+        assert_eq!(player.time_remaining().await, None);
+    }
+
+    #[tokio::test]
+    async fn position_fraction_unknown_length() {
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert_eq!(player.position_fraction().await, None);
+        assert!(player.seek_to_fraction(0.5).await.is_err());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn get_metadata_stable() {
+        let (player, server) = mock::connect(mock::MockPlayer::default()).await;
+
+        let empty = player.get_metadata_stable(2, Duration::from_millis(100)).await.unwrap();
+        assert!(empty.is_empty());
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            iface.get_mut().await.metadata = mock::metadata_with_trackid("/track/1");
+        });
+
+        let metadata = player.get_metadata_stable(3, Duration::from_millis(100)).await.unwrap();
+        assert_eq!(metadata.trackid.as_deref(), Some("/track/1"));
+    }
+
+    #[tokio::test]
+    async fn missing_trackid() {
+        let mut map = HashMap::new();
+        assert_eq!(Metadata::from(map.clone()).trackid, None);
+
+        map.insert("mpris:trackid".to_string(), OwnedValue::from(zbus::zvariant::Str::from("")));
+        assert_eq!(Metadata::from(map).trackid, None);
+
+        let mock = mock::MockPlayer::default();
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+        assert!(matches!(player.set_position(TrackId::new(), Duration::ZERO).await, Err(Error::InvalidTrackId(_))));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_trackid() {
+        // Sent as a string, as it can't be an object path
+        let metadata = HashMap::from([("mpris:trackid".to_string(), OwnedValue::from(zbus::zvariant::Str::from("not a path")))]);
+        let mock = mock::MockPlayer { metadata, position: 30_000_000, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        let trackid = player.get(properties::Metadata).await.unwrap().trackid.unwrap();
+        assert_eq!(trackid, "not a path");
+        assert!(matches!(player.set_position(trackid, Duration::ZERO).await, Err(Error::InvalidTrackId(_))));
+
+        player.seek_to(Duration::from_secs(10)).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["Seek(-20000000)"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn set_position_verified() {
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() };
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(player.set_position_verified(Duration::from_secs(10), Duration::from_secs(1)).await.unwrap());
+        assert_eq!(player.get(Position).await.unwrap(), Duration::from_secs(10));
+
+        // Accepts the call, but stays where it was, and doesn't report anything
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ignore_writes: true, ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(!player.set_position_verified(Duration::from_secs(10), Duration::from_secs(1)).await.unwrap());
+        assert_eq!(*calls.lock().unwrap(), vec!["SetPosition(/track/1, 10000000)"]);
+    }
+
+    #[tokio::test]
+    async fn seek_to_no_track() {
+        let mock = mock::MockPlayer { metadata: mock::metadata_with_trackid(NO_TRACK), ..Default::default() };
+        let calls = mock.calls.clone();
+        let (player, _server) = mock::connect(mock).await;
+
+        assert!(player.seek_to(Duration::from_secs(10)).await.is_err());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn subscribe_volume_smoothed() {
+        let (player, server) = mock::connect(mock::MockPlayer { volume: 0.0, ..Default::default() }).await;
+
+        let volumes = Arc::new(player).subscribe_volume_smoothed(Duration::from_millis(160)).await.unwrap();
+        futures::pin_mut!(volumes);
+        assert_eq!(volumes.next().await.unwrap().value, 0.0);
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.volume = 1.0;
+        iface.get().await.volume_changed(iface.signal_emitter()).await.unwrap();
+
+        let mut ramp = Vec::new();
+        while ramp.last() != Some(&1.0) {
+            ramp.push(volumes.next().await.unwrap().value);
+        }
+
+        assert!(ramp.len() > 5);
+        assert!(ramp.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[tokio::test]
+    async fn display_name() {
+        let (player, _server) = mock::connect(mock::MockPlayer::default()).await;
+
+        assert_eq!(player.short_name(), "mock");
+        // The mock has neither an identity, nor a desktop entry
+        assert_eq!(player.display_name().await, "mock");
+    }
+
+    #[tokio::test]
+    async fn subscribe_metadata_raw() {
+        let mut metadata = mock::metadata_with_trackid("/track/1");
+        metadata.insert("vendor:custom".to_string(), OwnedValue::from(42u32));
+        let (player, _server) = mock::connect(mock::MockPlayer { metadata, ..Default::default() }).await;
+
+        let raw = Arc::new(player).subscribe_metadata_raw().await.unwrap();
+        futures::pin_mut!(raw);
+
+        let first = raw.next().await.unwrap().value;
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.get("vendor:custom").unwrap().downcast_ref::<u32>().unwrap(), 42);
+    }
+
+    #[test]
+    fn position_estimation_edge_cases() {
+        use micros::{MAX_POSITION, advance};
+        let second = Duration::from_secs(1);
+
+        assert_eq!(advance(Duration::from_secs(10), second, 2.0), Duration::from_secs(12));
+        assert_eq!(advance(Duration::from_secs(10), second, 0.0), Duration::from_secs(10));
+
+        // Rates that make no sense count as 1.0
+        for rate in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -2.0] {
+            assert_eq!(advance(Duration::from_secs(10), second, rate), Duration::from_secs(11));
+        }
+
+        // Absurd positions and rates saturate, rather than overflowing
+        assert_eq!(advance(MAX_POSITION, second, 1.0), MAX_POSITION);
+        assert_eq!(advance(Duration::MAX, Duration::MAX, f64::MAX), MAX_POSITION);
+        assert_eq!(micros::duration_to_micros(advance(MAX_POSITION, second, 1e300)), i64::MAX);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_rate_change_while_paused() {
+        let mock = mock::MockPlayer { playback_status: String::from("Paused"), position: 10_000_000, ..Default::default() };
+        let (player, server) = mock::connect(mock).await;
+
+        let position = Arc::new(player).subscribe_position().await.unwrap();
+        futures::pin_mut!(position);
+        assert_eq!(position.next().await.unwrap().value, Duration::from_secs(10));
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+
+        // The rate changes while paused, then the playback resumes, so the new rate applies from there
+        iface.get_mut().await.rate = 2.0;
+        iface.get().await.rate_changed(iface.signal_emitter()).await.unwrap();
+        iface.get_mut().await.playback_status = String::from("Playing");
+        iface.get().await.playback_status_changed(iface.signal_emitter()).await.unwrap();
+
+        assert_eq!(position.next().await.unwrap().value, Duration::from_secs(10));
+        assert_eq!(position.next().await.unwrap().value, Duration::from_secs(12));
+
+        // Pausing keeps the position, rather than scaling all of it by the rate
+        iface.get_mut().await.playback_status = String::from("Paused");
+        iface.get().await.playback_status_changed(iface.signal_emitter()).await.unwrap();
+
+        let paused_at = position.next().await.unwrap().value;
+        assert!(paused_at == Duration::from_secs(12) || paused_at == Duration::from_secs(14), "{paused_at:?}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_bounded() {
+        let mut metadata = mock::metadata_with_trackid("/track/1");
+        metadata.insert("mpris:length".to_string(), OwnedValue::from(3_000_000i64));
+        let mock = mock::MockPlayer { playback_status: String::from("Playing"), position: 1_000_000, metadata, ..Default::default() };
+        let (player, server) = mock::connect(mock).await;
+
+        let position = Arc::new(player).subscribe_position_bounded().await.unwrap();
+        futures::pin_mut!(position);
+
+        let mut events = position.as_mut().map(|event| event.value).skip_while(|event| futures::future::ready(*event == streams::PositionEvent::Position(Duration::from_secs(1))));
+        assert_eq!(events.next().await.unwrap(), streams::PositionEvent::Position(Duration::from_secs(2)));
+        assert_eq!(events.next().await.unwrap(), streams::PositionEvent::Position(Duration::from_secs(3)));
+        assert_eq!(events.next().await.unwrap(), streams::PositionEvent::Ended);
+        // Clamped, and only ends once
+        assert_eq!(events.next().await.unwrap(), streams::PositionEvent::Position(Duration::from_secs(3)));
+
+        // The next track, without a length
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.metadata = mock::metadata_with_trackid("/track/2");
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+        mock::emit_seeked(&server, 0).await;
+
+        let mut events = position.map(|event| event.value).skip_while(|event| futures::future::ready(*event == streams::PositionEvent::Position(Duration::from_secs(3))));
+        assert_eq!(events.next().await.unwrap(), streams::PositionEvent::Position(Duration::ZERO));
+        assert_eq!(events.skip(3).next().await.unwrap(), streams::PositionEvent::Position(Duration::from_secs(4)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn position_play_grace() {
+        let (player, server) = mock::connect(mock::MockPlayer { playback_status: String::from("Paused"), ..Default::default() }).await;
+
+        let position = Arc::new(player).subscribe_position().await.unwrap().with_play_grace(Duration::from_millis(250));
+        futures::pin_mut!(position);
+        assert_eq!(position.next().await.unwrap().value, Duration::ZERO);
+
+        // The player starts playing, but only reports the real position a moment later, without a Seeked
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+        iface.get_mut().await.playback_status = String::from("Playing");
+        iface.get().await.playback_status_changed(iface.signal_emitter()).await.unwrap();
+        assert_eq!(position.next().await.unwrap().value, Duration::ZERO);
+
+        iface.get_mut().await.position = 30_000_000;
+        assert_eq!(position.next().await.unwrap().value, Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn track_changes() {
+        let (player, server) = mock::connect(mock::MockPlayer { metadata: mock::metadata_with_trackid("/track/1"), ..Default::default() }).await;
+
+        let changes = Arc::new(player).track_changes().await.unwrap();
+        futures::pin_mut!(changes);
+        assert_eq!(changes.next().await.unwrap().value.trackid.as_deref(), Some("/track/1"));
+
+        let iface = server.object_server().interface::<_, mock::MockPlayer>("/org/mpris/MediaPlayer2").await.unwrap();
+
+        // The same track with new art, then the next track
+        let mut refreshed = mock::metadata_with_trackid("/track/1");
+        refreshed.insert("mpris:artUrl".to_string(), OwnedValue::from(zbus::zvariant::Str::from("file:///art.png")));
+        iface.get_mut().await.metadata = refreshed;
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+
+        iface.get_mut().await.metadata = mock::metadata_with_trackid("/track/2");
+        iface.get().await.metadata_changed(iface.signal_emitter()).await.unwrap();
+
+        let changed = tokio::time::timeout(Duration::from_secs(1), changes.next()).await.unwrap().unwrap();
+        assert_eq!(changed.value.trackid.as_deref(), Some("/track/2"));
+    }
+
+    #[test]
+    fn effective_album_artist() {
+        let mut map = HashMap::new();
+        map.insert("xesam:artist".to_string(), OwnedValue::try_from(Value::from(vec!["Artist"])).unwrap());
+        assert_eq!(Metadata::from(map.clone()).effective_album_artist(), ["Artist"]);
+
+        map.insert("xesam:albumArtist".to_string(), OwnedValue::try_from(Value::from(vec!["Album Artist"])).unwrap());
+        assert_eq!(Metadata::from(map).effective_album_artist(), ["Album Artist"]);
+    }
+
+    #[test]
+    fn art_dimensions() {
+        use std::collections::HashMap;
+
+        let mut map: HashMap<String, OwnedValue> = HashMap::new();
+        assert_eq!(Metadata::from(map.clone()).art_dimensions(), None);
+
+        map.insert("mpris:artUrlWidth".to_string(), OwnedValue::from(640u32));
+        map.insert("mpris:artUrlHeight".to_string(), OwnedValue::from(480i32));
+        assert_eq!(Metadata::from(map.clone()).art_dimensions(), Some((640, 480)));
+
+        // Malformed values shouldn't break anything
+        map.insert("mpris:artUrlHeight".to_string(), OwnedValue::from(-1i32));
+        assert_eq!(Metadata::from(map).art_dimensions(), None);
+    }
+
+    #[test]
+    fn single_string_artists() {
+        use std::collections::HashMap;
+
+        let mut map: HashMap<String, OwnedValue> = HashMap::new();
+        map.insert("xesam:artist".to_string(), OwnedValue::try_from(Value::from("A, B & C")).unwrap());
+
+        let metadata = Metadata::from(map);
+        assert_eq!(metadata.artists, vec!["A, B & C"]);
+        assert_eq!(metadata.split_artists(), vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn live_stream_detection() {
+        use std::collections::HashMap;
+
+        let mut map: HashMap<String, OwnedValue> = HashMap::new();
+        map.insert("xesam:url".to_string(), OwnedValue::try_from(Value::from("https://radio.example/stream")).unwrap());
+        let metadata = Metadata::from(map.clone());
+        assert_eq!(metadata.url, "https://radio.example/stream");
+        assert!(metadata.is_stream());
+
+        map.insert("mpris:length".to_string(), OwnedValue::from(180_000_000i64));
+        assert!(!Metadata::from(map.clone()).is_stream());
+
+        map.remove("mpris:length");
+        map.insert("xesam:url".to_string(), OwnedValue::try_from(Value::from("file:///music/song.flac")).unwrap());
+        assert!(!Metadata::from(map).is_stream());
     }
 }
\ No newline at end of file
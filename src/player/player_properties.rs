@@ -0,0 +1,103 @@
+use std::{collections::HashMap, time::Duration};
+
+use zbus::zvariant::OwnedValue;
+
+use super::{Interface, Loop, Metadata, Playback, Playlist, PlaylistOrdering, TrackId, properties::{self, Property}};
+
+/// Every property of a [`Player`](super::Player) this crate knows about, read at once. Created by [`Player::properties`](super::Player::properties).
+/// <br>A field is `None` if the player doesn't provide that property (or sent something unparsable), so unlike using defaults, this tells what the player actually supports.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerProperties {
+    // org.mpris.MediaPlayer2
+
+    pub can_quit: Option<bool>,
+    pub fullscreen: Option<bool>,
+    pub can_set_fullscreen: Option<bool>,
+    pub can_raise: Option<bool>,
+    pub has_track_list: Option<bool>,
+    pub identity: Option<String>,
+    pub desktop_entry: Option<String>,
+    pub supported_uri_schemes: Option<Vec<String>>,
+    pub supported_mime_types: Option<Vec<String>>,
+
+    // org.mpris.MediaPlayer2.Player
+
+    pub playback_status: Option<Playback>,
+    pub loop_status: Option<Loop>,
+    pub rate: Option<f64>,
+    pub minimum_rate: Option<f64>,
+    pub maximum_rate: Option<f64>,
+    pub shuffle: Option<bool>,
+    pub metadata: Option<Metadata>,
+    pub volume: Option<f64>,
+    pub position: Option<Duration>,
+    pub can_go_next: Option<bool>,
+    pub can_go_previous: Option<bool>,
+    pub can_play: Option<bool>,
+    pub can_pause: Option<bool>,
+    pub can_seek: Option<bool>,
+    pub can_control: Option<bool>,
+
+    // org.mpris.MediaPlayer2.TrackList
+
+    pub tracks: Option<Vec<TrackId>>,
+    pub can_edit_tracks: Option<bool>,
+
+    // org.mpris.MediaPlayer2.Playlists
+
+    pub playlist_count: Option<u32>,
+    pub orderings: Option<Vec<PlaylistOrdering>>,
+    pub active_playlist: Option<Option<Playlist>>,
+}
+impl PlayerProperties {
+    /// Parses the properties out of the `GetAll` results of each interface
+    pub(crate) fn new(values: &HashMap<Interface, HashMap<String, OwnedValue>>) -> Self {
+        use properties::*;
+
+        Self {
+            can_quit: read(values, CanQuit),
+            fullscreen: read(values, Fullscreen),
+            can_set_fullscreen: read(values, CanSetFullscreen),
+            can_raise: read(values, CanRaise),
+            has_track_list: read(values, HasTrackList),
+            identity: read(values, Identity),
+            desktop_entry: read(values, DesktopEntry),
+            supported_uri_schemes: read(values, SupportedURIs),
+            supported_mime_types: read(values, SupportedMIMEs),
+
+            playback_status: read(values, PlaybackStatus),
+            loop_status: read(values, LoopStatus),
+            rate: read(values, Rate),
+            minimum_rate: read(values, MinimumRate),
+            maximum_rate: read(values, MaximumRate),
+            shuffle: read(values, Shuffle),
+            metadata: read(values, properties::Metadata),
+            volume: read(values, Volume),
+            position: read(values, Position),
+            can_go_next: read(values, CanGoNext),
+            can_go_previous: read(values, CanGoPrevious),
+            can_play: read(values, CanPlay),
+            can_pause: read(values, CanPause),
+            can_seek: read(values, CanSeek),
+            can_control: read(values, CanControl),
+
+            tracks: read(values, Tracks),
+            can_edit_tracks: read(values, CanEditTracks),
+
+            playlist_count: read(values, PlaylistCount),
+            orderings: read(values, Orderings),
+            active_playlist: read(values, ActivePlaylist),
+        }
+    }
+}
+
+/// Reads a single property from the values of its interface
+fn read<P>(values: &HashMap<Interface, HashMap<String, OwnedValue>>, property: P) -> Option<P::Output>
+where
+    P: Property,
+    P::ParseAs: TryFrom<OwnedValue>
+{
+    let value = values.get(&property.interface())?.get(property.name())?.try_clone().ok()?;
+
+    super::value::parse::<P::ParseAs>(value).map(|value| property.into_output(value))
+}
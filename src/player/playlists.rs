@@ -0,0 +1,38 @@
+//! Methods of the [`Interface::Playlists`], which only some players implement. See [`Player::interfaces`] to check.
+
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+
+use crate::Error;
+
+use super::{Interface, Player, PlaylistOrdering};
+
+/// A playlist of a player, see [`Player::get_playlists`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Playlist {
+    /// Identifies the playlist, for [`Player::activate_playlist`]
+    pub id: OwnedObjectPath,
+    /// The name to show to users
+    pub name: String,
+    /// The URI of an icon of the playlist, empty if there is none
+    pub icon: String
+}
+impl From<(OwnedObjectPath, String, String)> for Playlist {
+    fn from((id, name, icon): (OwnedObjectPath, String, String)) -> Self {
+        Self { id, name, icon }
+    }
+}
+
+impl Player {
+    /// Starts playing the playlist. The player may not start the playback right away.
+    pub async fn activate_playlist(&self, id: &ObjectPath<'_>) -> Result<(), Error> {
+        self.call_method("ActivatePlaylist", (id,), Interface::Playlists).await
+    }
+
+    /// Gets at most `max_count` playlists, starting from the `index`th one, in the `order` (or in the reverse order, if `reverse` is true).
+    /// <br>The orders the player supports are in [`properties::Orderings`](super::properties::Orderings), and the number of playlists in [`properties::PlaylistCount`](super::properties::PlaylistCount).
+    pub async fn get_playlists(&self, index: u32, max_count: u32, order: PlaylistOrdering, reverse: bool) -> Result<Vec<Playlist>, Error> {
+        let playlists: Vec<(OwnedObjectPath, String, String)> = self.call_method("GetPlaylists", (index, max_count, order.as_str(), reverse), Interface::Playlists).await?;
+
+        Ok(playlists.into_iter().map(Playlist::from).collect())
+    }
+}
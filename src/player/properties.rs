@@ -3,17 +3,18 @@
 use std::fmt::Debug;
 use std::{collections::HashMap, time::Duration};
 
-use zbus::zvariant::OwnedValue;
+use zbus::{export::serde::de::DeserializeOwned, zvariant::{OwnedObjectPath, OwnedValue}};
 
-use crate::{Loop, Metadata as Mtd, Playback};
+use crate::{Loop, Metadata as Mtd, Playback, Playlist, PlaylistOrdering, TrackId};
 use crate::player::enums::Interface;
+use crate::player::micros::micros_to_duration;
 
 
 /// Can be used to get some property from the bus.
 /// <br>Properties also may implement [WritableProperty], or [ControlWritableProperty] (but shouldn't implement both at the same time).
-pub trait Property : Debug {
+pub trait Property : Debug + Send + Sync {
     /// Parses form zbus's Value as this, with into_output transformations may be applied
-    type ParseAs: serde::de::DeserializeOwned + Send + 'static + Clone;
+    type ParseAs: DeserializeOwned + Send + 'static + Clone;
 
     /// The output type of the property 
     type Output:  Send + 'static;
@@ -27,6 +28,7 @@ pub trait Property : Debug {
     }
 
     /// Convert the parsed value into the final Output
+    #[allow(clippy::wrong_self_convention)]
     fn into_output(&self, value: Self::ParseAs) -> Self::Output;
 }
 
@@ -34,6 +36,7 @@ pub trait Property : Debug {
 /// <br> A [Property] should not implement both this and [ControlWritableProperty] at the same time!
 pub trait WritableProperty : Property {
     /// The opposite of [Property::into_output], as it converts the [Property::Output] into [Property::ParseAs]
+    #[allow(clippy::wrong_self_convention)]
     fn from_output(&self, value: Self::Output) -> Self::ParseAs;
 }
 
@@ -42,6 +45,7 @@ pub trait WritableProperty : Property {
 /// <br>According to the specs, this describes the player's implementation, rather than the current state, meaning this wont change after an object is registered.
 pub trait ControlWritableProperty : Property {
     /// The opposite of [Property::into_output], as it converts the [Property::Output] into [Property::ParseAs]
+    #[allow(clippy::wrong_self_convention)]
     fn from_output(&self, value: Self::Output) -> Self::ParseAs;
 }
 
@@ -106,7 +110,7 @@ impl Property for Fullscreen {
 }
 impl WritableProperty for Fullscreen {
     fn from_output(&self, value: Self::Output) -> Self::ParseAs {
-        value.into()
+        value
     }
 }
 
@@ -204,6 +208,7 @@ impl Property for DesktopEntry {
 
 pub const SUPPORTEDURIS: SupportedURIs = SupportedURIs;
 /// The URI schemes supported by the media player.This can be viewed as protocols supported by the player in almost all cases. 
+/// <br>See [`Player::supported_uri_schemes`](super::Player::supported_uri_schemes) for them as [`UriScheme`](super::UriScheme)s.
 #[derive(Debug)]
 pub struct SupportedURIs;
 impl Property for SupportedURIs {
@@ -309,7 +314,7 @@ impl Property for Rate {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -318,7 +323,7 @@ impl Property for Rate {
 }
 impl ControlWritableProperty for Rate {
     fn from_output(&self, value: Self::Output) -> Self::ParseAs {
-        value.into()
+        value
     }
 }
 
@@ -326,7 +331,7 @@ impl ControlWritableProperty for Rate {
 pub const MINIMUMRATE: MinimumRate = MinimumRate;
 /// The minimum value which the Rate property can take. Clients should not attempt to set the Rate property below this value.
 /// <br>Note that even if this value is 0.0 or negative, clients should not attempt to set the Rate property to 0.0.
-/// <br>This value should always be 1.0 or less, but some players might return [zbus::fdo::Error::NotSupported].
+/// <br>This value should always be 1.0 or less, but some players don't support it, and return [`Error::UnsupportedProperty`](crate::Error::UnsupportedProperty).
 #[derive(Debug)]
 pub struct MinimumRate;
 impl Property for MinimumRate {
@@ -338,7 +343,7 @@ impl Property for MinimumRate {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -349,7 +354,7 @@ impl Property for MinimumRate {
 
 pub const MAXIMUMRATE: MaximumRate = MaximumRate;
 /// The maximum value which the Rate property can take. Clients should not attempt to set the Rate property above this value.
-/// <br>This value should always be 1.0 or greater, but some players might return [zbus::fdo::Error::NotSupported].
+/// <br>This value should always be 1.0 or greater, but some players don't support it, and return [`Error::UnsupportedProperty`](crate::Error::UnsupportedProperty).
 #[derive(Debug)]
 pub struct MaximumRate;
 impl Property for MaximumRate {
@@ -361,7 +366,7 @@ impl Property for MaximumRate {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -385,7 +390,7 @@ impl Property for Position {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        Duration::from_micros(value as u64)
+        micros_to_duration(value)
     }
 
     fn name(&self) -> &'static str {
@@ -395,6 +400,7 @@ impl Property for Position {
 
 pub const SHUFFLE: Shuffle = Shuffle;
 /// A value of false indicates that playback is progressing linearly through a playlist, while true means playback is progressing through a playlist in some other order. 
+/// <br>This is optional, plenty of players (like browsers) don't have it, in which case reading it fails, rather than returning false. See [`Player::shuffle_state`](crate::Player::shuffle_state).
 #[derive(Debug)]
 pub struct Shuffle;
 impl Property for Shuffle {
@@ -406,7 +412,7 @@ impl Property for Shuffle {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -415,7 +421,7 @@ impl Property for Shuffle {
 }
 impl ControlWritableProperty for Shuffle {
     fn from_output(&self, value: Self::Output) -> Self::ParseAs {
-        value.into()
+        value
     }
 }
 
@@ -433,7 +439,7 @@ impl Property for Volume {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -442,7 +448,7 @@ impl Property for Volume {
 }
 impl ControlWritableProperty for Volume {
     fn from_output(&self, value: Self::Output) -> Self::ParseAs {
-        value.into()
+        value
     }
 }
 
@@ -484,7 +490,7 @@ impl Property for CanGoNext {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -507,7 +513,7 @@ impl Property for CanGoPrevious {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -530,7 +536,7 @@ impl Property for CanPlay {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -553,7 +559,7 @@ impl Property for CanPause {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
@@ -578,10 +584,129 @@ impl Property for CanSeek {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        value.into()
+        value
     }
 
     fn name(&self) -> &'static str {
         "CanSeek"
     }
-}
\ No newline at end of file
+}
+
+
+pub const TRACKS: Tracks = Tracks;
+/// The [trackids](crate::Metadata::trackid) of the tracks in the tracklist, in order. See [`Player::get_tracks_metadata`](super::Player::get_tracks_metadata) to get what they are.
+/// <br>Only on players that implement [`Interface::TrackList`].
+#[derive(Debug)]
+pub struct Tracks;
+impl Property for Tracks {
+    type Output = Vec<TrackId>;
+    type ParseAs = Vec<OwnedObjectPath>;
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value.into_iter().map(|path| path.to_string()).collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Tracks"
+    }
+}
+
+
+
+pub const CANEDITTRACKS: CanEditTracks = CanEditTracks;
+/// If false, calling [`Player::add_track`](super::Player::add_track) or [`Player::remove_track`](super::Player::remove_track) will have no effect, and may raise an error.
+/// <br>Only on players that implement [`Interface::TrackList`].
+#[derive(Debug)]
+pub struct CanEditTracks;
+impl Property for CanEditTracks {
+    type Output = bool;
+    type ParseAs = bool;
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value
+    }
+
+    fn name(&self) -> &'static str {
+        "CanEditTracks"
+    }
+}
+
+
+
+pub const PLAYLISTCOUNT: PlaylistCount = PlaylistCount;
+/// The number of playlists the player has.
+/// <br>Only on players that implement [`Interface::Playlists`].
+#[derive(Debug)]
+pub struct PlaylistCount;
+impl Property for PlaylistCount {
+    type Output = u32;
+    type ParseAs = u32;
+
+    fn interface(&self) -> Interface {
+        Interface::Playlists
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value
+    }
+
+    fn name(&self) -> &'static str {
+        "PlaylistCount"
+    }
+}
+
+
+
+pub const ORDERINGS: Orderings = Orderings;
+/// The [orders](PlaylistOrdering) [`Player::get_playlists`](super::Player::get_playlists) supports. Orderings that aren't in the specs are left out.
+/// <br>Only on players that implement [`Interface::Playlists`].
+#[derive(Debug)]
+pub struct Orderings;
+impl Property for Orderings {
+    type Output = Vec<PlaylistOrdering>;
+    type ParseAs = Vec<String>;
+
+    fn interface(&self) -> Interface {
+        Interface::Playlists
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value.iter().filter_map(|ordering| PlaylistOrdering::from_name(ordering)).collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Orderings"
+    }
+}
+
+
+
+pub const ACTIVEPLAYLIST: ActivePlaylist = ActivePlaylist;
+/// The playlist currently playing, or `None` if there is none (or the player doesn't know which one it is).
+/// <br>Only on players that implement [`Interface::Playlists`].
+#[derive(Debug)]
+pub struct ActivePlaylist;
+impl Property for ActivePlaylist {
+    type Output = Option<Playlist>;
+    type ParseAs = (bool, (OwnedObjectPath, String, String));
+
+    fn interface(&self) -> Interface {
+        Interface::Playlists
+    }
+
+    fn into_output(&self, (valid, playlist): Self::ParseAs) -> Self::Output {
+        valid.then(|| playlist.into())
+    }
+
+    fn name(&self) -> &'static str {
+        "ActivePlaylist"
+    }
+}
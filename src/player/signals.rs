@@ -1,16 +1,16 @@
 //! Types of the signals of a [`Player`](super::Player)
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
-use zbus::zvariant::DynamicDeserialize;
+use zbus::{export::serde::de::DeserializeOwned, zvariant::{DynamicDeserialize, OwnedObjectPath, OwnedValue}};
 
-use crate::player::Interface;
+use crate::player::{Interface, Metadata, Playlist, TrackId, micros::micros_to_duration};
 
 
 /// A dbus signal, check [`Player::subscribe`](super::Player::subscribe)
 pub trait Signal {
     /// Parses form zbus's Value as this, with into_output transformations may be applied
-    type ParseAs: serde::de::DeserializeOwned + DynamicDeserialize<'static> + Send + 'static;
+    type ParseAs: DeserializeOwned + for<'d> DynamicDeserialize<'d> + Send + 'static;
 
     /// The output type of the property 
     type Output:  Send + 'static;
@@ -24,6 +24,7 @@ pub trait Signal {
     }
 
     /// Convert the parsed value into the final Output
+    #[allow(clippy::wrong_self_convention)]
     fn into_output(&self, value: Self::ParseAs) -> Self::Output;
 }
 
@@ -47,6 +48,173 @@ impl Signal for Seeked {
     }
 
     fn into_output(&self, value: Self::ParseAs) -> Self::Output {
-        Duration::from_micros(value as u64)
+        micros_to_duration(value)
     }
-}
\ No newline at end of file
+}
+
+
+
+pub const TRACKLISTREPLACED: TrackListReplaced = TrackListReplaced;
+/// Indicates that the whole tracklist was replaced, like when a new album is opened. Yields the new [`Tracks`](super::properties::Tracks), and the trackid of the current track.
+/// <br>Only on players that implement [`Interface::TrackList`].
+pub struct TrackListReplaced;
+impl Signal for TrackListReplaced {
+    type Output = (Vec<TrackId>, TrackId);
+    type ParseAs = (Vec<OwnedObjectPath>, OwnedObjectPath);
+
+    fn name(&self) -> &'static str {
+        "TrackListReplaced"
+    }
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, (tracks, current): Self::ParseAs) -> Self::Output {
+        (tracks.into_iter().map(|track| track.to_string()).collect(), current.to_string())
+    }
+}
+
+
+
+pub const TRACKADDED: TrackAdded = TrackAdded;
+/// Indicates that a track was added to the tracklist. Yields its metadata, and the trackid of the track it was added after, which is [`NO_TRACK`](crate::NO_TRACK) if it was added to the start.
+/// <br>Only on players that implement [`Interface::TrackList`].
+pub struct TrackAdded;
+impl Signal for TrackAdded {
+    type Output = (Metadata, TrackId);
+    type ParseAs = (HashMap<String, OwnedValue>, OwnedObjectPath);
+
+    fn name(&self) -> &'static str {
+        "TrackAdded"
+    }
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, (metadata, after): Self::ParseAs) -> Self::Output {
+        (metadata.into(), after.to_string())
+    }
+}
+
+
+
+pub const TRACKREMOVED: TrackRemoved = TrackRemoved;
+/// Indicates that a track was removed from the tracklist. Yields its trackid.
+/// <br>Only on players that implement [`Interface::TrackList`].
+pub struct TrackRemoved;
+impl Signal for TrackRemoved {
+    type Output = TrackId;
+    type ParseAs = OwnedObjectPath;
+
+    fn name(&self) -> &'static str {
+        "TrackRemoved"
+    }
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value.to_string()
+    }
+}
+
+
+
+pub const TRACKMETADATACHANGED: TrackMetadataChanged = TrackMetadataChanged;
+/// Indicates that the metadata of a track in the tracklist changed. Yields the trackid, and the new metadata.
+/// <br>The trackid in the metadata may differ from the first one, if the track got a new one.
+/// <br>Only on players that implement [`Interface::TrackList`].
+pub struct TrackMetadataChanged;
+impl Signal for TrackMetadataChanged {
+    type Output = (TrackId, Metadata);
+    type ParseAs = (OwnedObjectPath, HashMap<String, OwnedValue>);
+
+    fn name(&self) -> &'static str {
+        "TrackMetadataChanged"
+    }
+
+    fn interface(&self) -> Interface {
+        Interface::TrackList
+    }
+
+    fn into_output(&self, (track_id, metadata): Self::ParseAs) -> Self::Output {
+        (track_id.to_string(), metadata.into())
+    }
+}
+
+
+
+pub const PLAYLISTCHANGED: PlaylistChanged = PlaylistChanged;
+/// Indicates that the name or the icon of a playlist changed. Yields the playlist with the new ones.
+/// <br>Only on players that implement [`Interface::Playlists`].
+pub struct PlaylistChanged;
+impl Signal for PlaylistChanged {
+    type Output = Playlist;
+    type ParseAs = (OwnedObjectPath, String, String);
+
+    fn name(&self) -> &'static str {
+        "PlaylistChanged"
+    }
+
+    fn interface(&self) -> Interface {
+        Interface::Playlists
+    }
+
+    fn into_output(&self, value: Self::ParseAs) -> Self::Output {
+        value.into()
+    }
+}
+
+/// Any signal of a [`Player`](super::Player) on one of the MPRIS [interfaces](Interface), yielded by [`Player::subscribe_all_signals`](super::Player::subscribe_all_signals)
+#[derive(Debug, Clone)]
+pub enum AnySignal {
+    /// See [`Seeked`]
+    Seeked(Duration),
+    /// See [`TrackListReplaced`]
+    TrackListReplaced(Vec<TrackId>, TrackId),
+    /// See [`TrackAdded`]
+    TrackAdded(Box<Metadata>, TrackId),
+    /// See [`TrackRemoved`]
+    TrackRemoved(TrackId),
+    /// See [`TrackMetadataChanged`]
+    TrackMetadataChanged(TrackId, Box<Metadata>),
+    /// See [`PlaylistChanged`]
+    PlaylistChanged(Playlist),
+    /// A signal this crate doesn't have a type for (like the ones of extensions some players add), with the message to parse it from
+    Other {
+        interface: Interface,
+        member: String,
+        message: zbus::Message
+    }
+}
+impl AnySignal {
+    /// Parses the signal message, returns `None` if it's not on an MPRIS interface, or its body is malformed
+    pub(crate) fn from_message(message: zbus::Message) -> Option<Self> {
+        let header = message.header();
+        let interface = Interface::from_name(header.interface()?.as_str())?;
+        let member = header.member()?.to_string();
+
+        let body = message.body();
+        match (interface, member.as_str()) {
+            (Interface::Player, "Seeked") => body.deserialize().ok().map(|value| AnySignal::Seeked(Seeked.into_output(value))),
+            (Interface::TrackList, "TrackListReplaced") => body.deserialize().ok().map(|value| {
+                let (tracks, current) = TrackListReplaced.into_output(value);
+                AnySignal::TrackListReplaced(tracks, current)
+            }),
+            (Interface::TrackList, "TrackAdded") => body.deserialize().ok().map(|value| {
+                let (metadata, after) = TrackAdded.into_output(value);
+                AnySignal::TrackAdded(Box::new(metadata), after)
+            }),
+            (Interface::TrackList, "TrackRemoved") => body.deserialize().ok().map(|value| AnySignal::TrackRemoved(TrackRemoved.into_output(value))),
+            (Interface::TrackList, "TrackMetadataChanged") => body.deserialize().ok().map(|value| {
+                let (track_id, metadata) = TrackMetadataChanged.into_output(value);
+                AnySignal::TrackMetadataChanged(track_id, Box::new(metadata))
+            }),
+            (Interface::Playlists, "PlaylistChanged") => body.deserialize().ok().map(|value| AnySignal::PlaylistChanged(PlaylistChanged.into_output(value))),
+            _ => Some(AnySignal::Other { interface, member, message: message.clone() })
+        }
+    }
+}
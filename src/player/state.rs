@@ -0,0 +1,14 @@
+use crate::Playback;
+
+/// The playback state of a [`Player`](super::Player) that can be put back later, like after lowering the music for a notification.
+/// <br>Created by [`Player::capture_state`](super::Player::capture_state), restored with [`Player::restore_state`](super::Player::restore_state).
+/// Fields are `None` if the player didn't provide them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerState {
+    /// See [`properties::Volume`](super::properties::Volume)
+    pub volume: Option<f64>,
+    /// See [`properties::PlaybackStatus`](super::properties::PlaybackStatus)
+    pub playback: Option<Playback>,
+    /// See [`properties::Rate`](super::properties::Rate)
+    pub rate: Option<f64>,
+}
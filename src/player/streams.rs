@@ -1,15 +1,14 @@
 //! Provides a few useful streams to make working with a [`Player`](super::Player) easier
 
 
-use std::{ops::Deref, pin::Pin, task::{Context, Poll}, time::Duration};
+use std::{ops::Deref, pin::Pin, sync::Arc, task::{Context, Poll}, time::Duration};
 
-use futures::Stream;
+use futures::{Stream, StreamExt, pin_mut, stream::{self, FusedStream as _}};
 use pin_project::pin_project;
-use serde::de::DeserializeOwned;
-use tokio::time::{Instant, Sleep, sleep_until};
-use zbus::{names::OwnedBusName, proxy::{PropertyStream, SignalStream}, zvariant::{OwnedValue, Type}};
+use tokio::{sync::broadcast::{self, error::RecvError}, time::{Instant, Sleep, sleep_until}};
+use zbus::{AsyncDrop, export::serde::de::DeserializeOwned, names::OwnedBusName, proxy::{OwnerChangedStream, PropertyStream, SignalStream}, zvariant::{OwnedValue, Type}};
 
-use crate::{Playback, player::Property, properties::{PlaybackStatus, Rate}, signals::{Seeked, Signal}};
+use crate::{Error, Loop, MetadataDiff, Playback, Player, player::{Property, micros::advance}, properties::{LoopStatus, Metadata, PlaybackStatus, Position, Rate, Shuffle, Volume}, signals::{Seeked, Signal}};
 
 #[derive(Debug, Clone)]
 /// Contains the value yielded by a stream, and the [`Player`](super::Player)'s name that yielded it.
@@ -30,6 +29,9 @@ impl<T> StreamYield<T> {
 
 /// Returns the current position of the media of a [`Player`](super::Player) every second, without polling the player.
 /// <br><br>Note: this doesn't take into account the length of the media, as it might not be provided, thus the returned position could be longer than the length of the media.
+/// See [`BoundedPositionStream`] for one that does.
+/// <br>For players that never emit [`Seeked`], see [`with_resync`](Self::with_resync).
+/// <br><br>The time is read from tokio's clock, so in tests `tokio::time::pause` and `tokio::time::advance` drive both the ticks and the estimation.
 #[pin_project]
 pub struct PositionStream<'a> {
     #[pin]
@@ -50,11 +52,33 @@ pub struct PositionStream<'a> {
     playback: Playback,
     position: Duration,
 
+    // Only set if periodic re-reading of the Position was requested
+    resync: Option<Resync<'a>>,
+
+    // Only set if re-reading the Position after the playback starts was requested
+    play_grace: Option<PlayGrace<'a>>,
+
+    player: Arc<Player>,
     player_name: OwnedBusName,
 }
+
+/// State of the periodic re-anchoring of a [`PositionStream`]
+struct Resync<'a> {
+    interval: Duration,
+    sleep: Pin<Box<Sleep>>,
+    pending: Option<PendingParse<'a, Duration>>,
+}
+
+/// State of the one-off re-read of a [`PositionStream`] after the playback starts, see [`PositionStream::with_play_grace`]
+struct PlayGrace<'a> {
+    delay: Duration,
+    sleep: Option<Pin<Box<Sleep>>>,
+    pending: Option<PendingParse<'a, Duration>>,
+}
+
 impl<'a> PositionStream<'a> {
     pub fn new(
-        player_name: OwnedBusName,
+        player: Arc<Player>,
         playback_stream: ParsedPropertyStream<'a, PlaybackStatus>,
         initial_playback: Playback,
         rate_stream: ParsedPropertyStream<'a, Rate>,
@@ -66,12 +90,52 @@ impl<'a> PositionStream<'a> {
             playback_stream, 
             rate_stream, 
             seeked_stream, 
-            sleep: Sleep::from(sleep_until(Instant::now())), // The stream be called instantly when the first poll happens
+            sleep: sleep_until(Instant::now()), // The stream be called instantly when the first poll happens
             last_tick: Instant::now(),
             rate: initial_rate, 
             playback: initial_playback, 
             position: initial_position,
-            player_name
+            resync: None,
+            play_grace: None,
+            player_name: player.dbus_name(),
+            player
+        }
+    }
+
+    /// Re-reads the authoritative [`Position`] of the player every `interval`, and re-anchors the estimation to it.
+    /// <br>Some players change the position without ever emitting [`Seeked`] (expecting the clients to poll instead), which makes the estimation drift.
+    /// <br><br>This is off by default, as well-behaved players don't need it. Something like 10 seconds is usually a good compromise.
+    pub fn with_resync(mut self, interval: Duration) -> Self {
+        self.resync = Some(Resync {
+            interval,
+            sleep: Box::pin(sleep_until(Instant::now() + interval)),
+            pending: None
+        });
+        self
+    }
+
+    /// Re-reads the [`Position`] once, `delay` after the playback starts, and re-anchors the estimation to it.
+    /// <br>Some players report a position of zero for a moment after starting to play, until the playback really begins,
+    /// which would make the estimation jump back to the start. Something like 250 milliseconds is usually enough.
+    /// <br><br>This is off by default. [`with_resync`](Self::with_resync) fixes the same eventually, but only on its next re-read.
+    pub fn with_play_grace(mut self, delay: Duration) -> Self {
+        self.play_grace = Some(PlayGrace {
+            delay,
+            sleep: None,
+            pending: None
+        });
+        self
+    }
+
+    /// Turns this into a [`HeartbeatPositionStream`], which yields the position at least every `interval`, even if nothing changed.
+    /// <br>For UIs that want a guaranteed pulse to redraw on. The items tell apart the real changes from the heartbeats.
+    pub fn with_heartbeat(self, interval: Duration) -> HeartbeatPositionStream<'a> {
+        HeartbeatPositionStream {
+            player_name: self.player_name.clone(),
+            inner: self,
+            sleep: sleep_until(Instant::now() + interval),
+            interval,
+            last: None
         }
     }
 }
@@ -94,7 +158,7 @@ impl<'a> Stream for PositionStream<'a> {
                 if *this.playback == Playback::Playing {
                     // How much time passsed since the last tick
                     let delta = Instant::now() - *this.last_tick;
-                    let new_position = Duration::from_micros((this.position.as_micros() as f64 + (delta.as_micros() as f64 * old_rate)) as u64);
+                    let new_position = advance(*this.position, delta, old_rate);
 
                     this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
 
@@ -116,16 +180,24 @@ impl<'a> Stream for PositionStream<'a> {
                 let old_playback = *this.playback;
                 *this.playback = new_playback.value;
 
-                this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
-
+                // Only reschedule the tick if something is yielded, as the first value of the stream is the unchanged state,
+                // which would otherwise delay the first tick
                 match (old_playback, *this.playback) {
                     (Playback::Paused | Playback::Stopped, Playback::Playing) => {
+                        this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
                         *this.last_tick = Instant::now();
+
+                        if let Some(grace) = this.play_grace.as_mut() {
+                            grace.sleep = Some(Box::pin(sleep_until(Instant::now() + grace.delay)));
+                            grace.pending = None;
+                        }
                         return Ready(Some(StreamYield::new(this.player_name.clone(), *this.position)))
                     },
                     (Playback::Playing, Playback::Paused | Playback::Stopped) => {
+                        this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
+                        // Only the time since the last tick is scaled by the rate, the position already accounts for it
                         let delta = Instant::now() - *this.last_tick;
-                        *this.position = Duration::from_micros(((*this.position + delta).as_micros() as f64 * *this.rate) as u64);
+                        *this.position = advance(*this.position, delta, *this.rate);
                         *this.last_tick = Instant::now();
 
                         return Ready(Some(StreamYield::new(this.player_name.clone(), *this.position)));
@@ -149,13 +221,67 @@ impl<'a> Stream for PositionStream<'a> {
             }
         }
 
+        if let Some(resync) = this.resync.as_mut() {
+            loop {
+                if let Some(fut) = resync.pending.as_mut() {
+                    match fut.as_mut().poll(cx) {
+                        Pending => break,
+                        Ready(result) => {
+                            resync.pending = None;
+                            resync.sleep.as_mut().reset(Instant::now() + resync.interval);
+
+                            // A failed read is not fatal, the estimation just continues until the next resync
+                            if let Ok(position) = result {
+                                *this.position = position;
+                                *this.last_tick = Instant::now();
+
+                                this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
+
+                                return Ready(Some(StreamYield::new(this.player_name.clone(), position)));
+                            }
+                        }
+                    }
+                }
+
+                match resync.sleep.as_mut().poll(cx) {
+                    Pending => break,
+                    Ready(_) => {
+                        let player = this.player.clone();
+                        resync.pending = Some(Box::pin(async move { player.get(Position).await }));
+                    }
+                }
+            }
+        }
+
+        if let Some(grace) = this.play_grace.as_mut() {
+            if let Some(sleep) = grace.sleep.as_mut() && sleep.as_mut().poll(cx).is_ready() {
+                let player = this.player.clone();
+                grace.sleep = None;
+                grace.pending = Some(Box::pin(async move { player.get(Position).await }));
+            }
+
+            if let Some(fut) = grace.pending.as_mut() && let Ready(result) = fut.as_mut().poll(cx) {
+                grace.pending = None;
+
+                // Like with the resync, a failed read just leaves the estimation as it is
+                if let Ok(position) = result {
+                    *this.position = position;
+                    *this.last_tick = Instant::now();
+
+                    this.sleep.set(sleep_until(Instant::now() + Duration::from_secs(1)));
+
+                    return Ready(Some(StreamYield::new(this.player_name.clone(), position)));
+                }
+            }
+        }
+
         match this.sleep.as_mut().poll(cx) {
             Pending => Pending,
             Ready(_) => {
                 match *this.playback {
                     Playback::Playing => {
                         let delta = Instant::now() - *this.last_tick;
-                        let new_position = Duration::from_micros((this.position.as_micros() as f64 + (delta.as_micros() as f64 * *this.rate)) as u64);
+                        let new_position = advance(*this.position, delta, *this.rate);
 
                         *this.position = new_position;
                     },
@@ -174,9 +300,600 @@ impl<'a> Stream for PositionStream<'a> {
 }
 
 
+/// An item of the [`HeartbeatPositionStream`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionTick {
+    /// The position changed
+    Changed(Duration),
+    /// Nothing changed, this is just the pulse
+    Heartbeat(Duration)
+}
+impl PositionTick {
+    /// The position, regardless if it changed or not
+    pub fn position(&self) -> Duration {
+        match self {
+            Self::Changed(position) | Self::Heartbeat(position) => *position
+        }
+    }
+}
+
+/// A [`PositionStream`] that yields at least every `interval`, even while paused. Created by [`PositionStream::with_heartbeat`].
+#[pin_project]
+pub struct HeartbeatPositionStream<'a> {
+    #[pin]
+    inner: PositionStream<'a>,
+    #[pin]
+    sleep: Sleep,
+    interval: Duration,
+    last: Option<Duration>,
+
+    player_name: OwnedBusName,
+}
+impl<'a> Stream for HeartbeatPositionStream<'a> {
+    type Item = StreamYield<PositionTick>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        match this.inner.as_mut().poll_next(cx) {
+            Ready(None) => return Ready(None),
+            Ready(Some(new)) => {
+                this.sleep.as_mut().reset(Instant::now() + *this.interval);
+
+                // The inner stream also ticks while paused, those are heartbeats too
+                let tick = if *this.last == Some(new.value) {
+                    PositionTick::Heartbeat(new.value)
+                } else {
+                    PositionTick::Changed(new.value)
+                };
+                *this.last = Some(new.value);
+
+                return Ready(Some(StreamYield::new(new.player_name, tick)));
+            },
+            Pending => {}
+        }
+
+        loop {
+            match this.sleep.as_mut().poll(cx) {
+                Pending => return Pending,
+                Ready(_) => {
+                    this.sleep.as_mut().reset(Instant::now() + *this.interval);
+
+                    // Nothing to repeat before the first position
+                    if let Some(position) = *this.last {
+                        return Ready(Some(StreamYield::new(this.player_name.clone(), PositionTick::Heartbeat(position))));
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+/// An item of the [`BoundedPositionStream`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionEvent {
+    /// The (estimated) position, which is never past the length of the track
+    Position(Duration),
+    /// The playback reached the end of the track. Yielded once per track, right after the position that reached it.
+    Ended
+}
+
+/// A [`PositionStream`] that knows the length of the track, so the position doesn't overshoot it. Created by [`Player::subscribe_position_bounded`](super::Player::subscribe_position_bounded).
+/// <br>If the [`Metadata`] has no length, the positions are yielded unbounded, like by [`PositionStream`], and [`PositionEvent::Ended`] never comes.
+#[pin_project]
+pub struct BoundedPositionStream<'a> {
+    #[pin]
+    inner: PositionStream<'a>,
+    #[pin]
+    metadata_stream: ParsedPropertyStream<'a, Metadata>,
+    trackid: Option<crate::TrackId>,
+    length: Option<Duration>,
+    // If Ended was already yielded for the current track
+    ended: bool,
+    // If Ended has to be yielded on the next poll
+    pending_end: bool,
+
+    player_name: OwnedBusName,
+}
+impl<'a> BoundedPositionStream<'a> {
+    pub fn new(inner: PositionStream<'a>, metadata_stream: ParsedPropertyStream<'a, Metadata>, initial_metadata: crate::Metadata) -> Self {
+        Self {
+            player_name: inner.player_name.clone(),
+            inner,
+            metadata_stream,
+            trackid: initial_metadata.trackid,
+            length: initial_metadata.length,
+            ended: false,
+            pending_end: false
+        }
+    }
+}
+impl<'a> Stream for BoundedPositionStream<'a> {
+    type Item = StreamYield<PositionEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        if *this.pending_end {
+            *this.pending_end = false;
+            return Ready(Some(StreamYield::new(this.player_name.clone(), PositionEvent::Ended)));
+        }
+
+        // Polled until pending, to register the waker
+        loop {
+            match this.metadata_stream.as_mut().poll_next(cx) {
+                Pending => break,
+                Ready(None) => return Ready(None),
+                Ready(Some(new)) => {
+                    // A new track can end again
+                    if new.value.trackid != *this.trackid || new.value.length != *this.length {
+                        *this.trackid = new.value.trackid;
+                        *this.length = new.value.length;
+                        *this.ended = false;
+                    }
+                }
+            }
+        }
+
+        match this.inner.as_mut().poll_next(cx) {
+            Pending => Pending,
+            Ready(None) => Ready(None),
+            Ready(Some(new)) => {
+                let Some(length) = *this.length else {
+                    return Ready(Some(StreamYield::new(new.player_name, PositionEvent::Position(new.value))));
+                };
+
+                let position = new.value.min(length);
+                if position < length {
+                    // Seeked back, so it can end again
+                    *this.ended = false;
+                } else if !*this.ended && this.inner.playback == Playback::Playing {
+                    *this.ended = true;
+                    *this.pending_end = true;
+                    cx.waker().wake_by_ref();
+                }
+
+                Ready(Some(StreamYield::new(new.player_name, PositionEvent::Position(position))))
+            }
+        }
+    }
+}
+
+/// Reads the [`Position`] of a [`Player`](super::Player) every `interval`, and yields it.
+/// <br>This is the fallback for minimal players that neither emit [`Seeked`], nor report the changes of their playback, thus [`PositionStream`] never updates.
+/// <br><br>It trades bus traffic for correctness, so prefer [`PositionStream`] if the player behaves.
+/// <br>Failed reads are skipped, the stream only ends when the player leaves the bus.
+#[pin_project]
+pub struct PollingPositionStream {
+    #[pin]
+    sleep: Sleep,
+    interval: Duration,
+    pending: Option<PendingParse<'static, Duration>>,
+
+    player: Arc<Player>,
+    player_name: OwnedBusName,
+}
+impl PollingPositionStream {
+    pub fn new(player: Arc<Player>, interval: Duration) -> Self {
+        Self {
+            sleep: sleep_until(Instant::now()), // The first read happens when the stream is first polled
+            interval,
+            pending: None,
+            player_name: player.dbus_name(),
+            player
+        }
+    }
+}
+impl Stream for PollingPositionStream {
+    type Item = StreamYield<Duration>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        loop {
+            if let Some(fut) = this.pending.as_mut() {
+                match fut.as_mut().poll(cx) {
+                    Pending => return Pending,
+                    Ready(result) => {
+                        *this.pending = None;
+                        this.sleep.as_mut().reset(Instant::now() + *this.interval);
+
+                        match result {
+                            Ok(position) => return Ready(Some(StreamYield::new(this.player_name.clone(), position))),
+                            // The player is not coming back, so the stream ends
+                            Err(Error::PlayerGone) => return Ready(None),
+                            // Might be a one-off failure, so it's tried again on the next tick
+                            Err(_) => continue
+                        }
+                    }
+                }
+            }
+
+            match this.sleep.as_mut().poll(cx) {
+                Pending => return Pending,
+                Ready(_) => {
+                    let player = this.player.clone();
+                    *this.pending = Some(Box::pin(async move { player.get(Position).await }));
+                }
+            }
+        }
+    }
+}
+
+
+/// Yields which fields of the [`Metadata`](crate::Metadata) changed, every time the player reports a new one.
+/// <br>Updates that don't change any field are skipped. Created by [`Player::subscribe_metadata_diff`].
+#[pin_project]
+pub struct MetadataDiffStream<'a> {
+    #[pin]
+    metadata_stream: ParsedPropertyStream<'a, Metadata>,
+    last: Option<crate::Metadata>
+}
+impl<'a> MetadataDiffStream<'a> {
+    pub fn new(metadata_stream: ParsedPropertyStream<'a, Metadata>) -> Self {
+        Self {
+            metadata_stream,
+            last: None
+        }
+    }
+}
+impl<'a> Stream for MetadataDiffStream<'a> {
+    type Item = StreamYield<MetadataDiff>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        loop {
+            match this.metadata_stream.as_mut().poll_next(cx) {
+                Pending => return Pending,
+                Ready(None) => return Ready(None),
+                Ready(Some(new)) => {
+                    // The first value is the current state, there is nothing to compare it to
+                    let diff = this.last.as_ref().map(|last| last.diff(&new.value));
+                    *this.last = Some(new.value);
+
+                    if let Some(diff) = diff && !diff.is_empty() {
+                        return Ready(Some(StreamYield::new(new.player_name, diff)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+/// What a player is playing right now, yielded by the [`NowPlayingStream`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NowPlaying {
+    pub metadata: crate::Metadata,
+    /// The (estimated) position, as in [`PositionStream`]
+    pub position: Duration,
+    /// The length of the track, the same as the [`Metadata`](crate::Metadata)'s
+    pub length: Option<Duration>,
+    pub playback: Playback
+}
+
+/// Combines the [`PositionStream`] with the [`Metadata`](crate::Metadata) and the [`PlaybackStatus`], yielding a [`NowPlaying`] every time any of them changes.
+/// <br>Handy for "title - 1:23 / 4:56" like widgets. Created by [`Player::subscribe_now_playing`].
+#[pin_project]
+pub struct NowPlayingStream<'a> {
+    #[pin]
+    position_stream: PositionStream<'a>,
+    #[pin]
+    metadata_stream: ParsedPropertyStream<'a, Metadata>,
+    #[pin]
+    playback_stream: ParsedPropertyStream<'a, PlaybackStatus>,
+    current: NowPlaying,
+    player_name: OwnedBusName
+}
+impl<'a> NowPlayingStream<'a> {
+    pub fn new(
+        player_name: OwnedBusName,
+        position_stream: PositionStream<'a>,
+        metadata_stream: ParsedPropertyStream<'a, Metadata>,
+        initial_metadata: crate::Metadata,
+        playback_stream: ParsedPropertyStream<'a, PlaybackStatus>,
+        initial_playback: Playback
+    ) -> Self {
+        Self {
+            position_stream,
+            metadata_stream,
+            playback_stream,
+            current: NowPlaying {
+                length: initial_metadata.length,
+                metadata: initial_metadata,
+                position: Duration::ZERO,
+                playback: initial_playback
+            },
+            player_name
+        }
+    }
+}
+impl<'a> Stream for NowPlayingStream<'a> {
+    type Item = StreamYield<NowPlaying>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        loop {
+            // If any of them yielded something, it has to be polled again to register the waker, even if nothing changed
+            let mut progressed = false;
+            let mut changed = false;
+
+            // The first item of the property streams is the current state, which is only yielded if it's different from the initial one
+            match this.metadata_stream.as_mut().poll_next(cx) {
+                Ready(None) => return Ready(None),
+                Ready(Some(new)) => {
+                    progressed = true;
+                    if new.value != this.current.metadata {
+                        this.current.length = new.value.length;
+                        this.current.metadata = new.value;
+                        changed = true;
+                    }
+                },
+                Pending => {}
+            }
+
+            match this.playback_stream.as_mut().poll_next(cx) {
+                Ready(None) => return Ready(None),
+                Ready(Some(new)) => {
+                    progressed = true;
+                    if new.value != this.current.playback {
+                        this.current.playback = new.value;
+                        changed = true;
+                    }
+                },
+                Pending => {}
+            }
+
+            match this.position_stream.as_mut().poll_next(cx) {
+                Ready(None) => return Ready(None),
+                Ready(Some(new)) => {
+                    this.current.position = new.value;
+                    changed = true;
+                },
+                Pending => {}
+            }
+
+            if changed {
+                return Ready(Some(StreamYield::new(this.player_name.clone(), this.current.clone())));
+            }
+            if !progressed {
+                return Pending;
+            }
+        }
+    }
+}
+
+/// Something that happened to a player, yielded by the [`PlayerUpdateStream`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerUpdate {
+    Playback(Playback),
+    /// Boxed, as it's much larger than the rest
+    Metadata(Box<crate::Metadata>),
+    /// The player [seeked](Seeked) to this position
+    Seeked(Duration),
+    Volume(f64),
+    Rate(f64),
+    Loop(Loop),
+    Shuffle(bool)
+}
+
+/// Merges the streams of the commonly used properties and the [`Seeked`] signal into a single stream of [`PlayerUpdate`]s.
+/// <br>Like with [`ParsedPropertyStream`], the first item of each property is its current value. Properties the player doesn't have are simply never yielded.
+/// <br>Created by [`Player::events`].
+/// <br><br>It owns every subscription it was created with, so when switching to another player, dropping it (or [`unsubscribe`](Self::unsubscribe)) tears all of them down at once.
+#[pin_project]
+pub struct PlayerUpdateStream<'a> {
+    #[pin]
+    properties: stream::SelectAll<stream::BoxStream<'a, StreamYield<PlayerUpdate>>>,
+    #[pin]
+    seeked_stream: stream::Fuse<ParsedSignalStream<'a, Seeked>>
+}
+impl<'a> PlayerUpdateStream<'a> {
+    pub fn new(
+        playback_stream: ParsedPropertyStream<'a, PlaybackStatus>,
+        metadata_stream: ParsedPropertyStream<'a, Metadata>,
+        volume_stream: ParsedPropertyStream<'a, Volume>,
+        rate_stream: ParsedPropertyStream<'a, Rate>,
+        loop_stream: ParsedPropertyStream<'a, LoopStatus>,
+        shuffle_stream: ParsedPropertyStream<'a, Shuffle>,
+        seeked_stream: ParsedSignalStream<'a, Seeked>
+    ) -> Self {
+        fn updates<'a, P>(stream: ParsedPropertyStream<'a, P>, update: fn(P::Output) -> PlayerUpdate) -> stream::BoxStream<'a, StreamYield<PlayerUpdate>>
+        where
+            P: Property + Unpin + Send + 'static,
+            P::ParseAs: TryFrom<OwnedValue>
+        {
+            stream.map(move |new| StreamYield::new(new.player_name, update(new.value))).boxed()
+        }
+
+        Self {
+            properties: stream::select_all([
+                updates(playback_stream, PlayerUpdate::Playback),
+                updates(metadata_stream, |metadata| PlayerUpdate::Metadata(Box::new(metadata))),
+                updates(volume_stream, PlayerUpdate::Volume),
+                updates(rate_stream, PlayerUpdate::Rate),
+                updates(loop_stream, PlayerUpdate::Loop),
+                updates(shuffle_stream, PlayerUpdate::Shuffle),
+            ]),
+            seeked_stream: seeked_stream.fuse()
+        }
+    }
+
+    /// Drops the stream, and waits until every match rule it added is removed from the bus, like [`ParsedSignalStream::unsubscribe`].
+    /// <br>The property streams share the `PropertiesChanged` subscription of the [`Player`], so only the [`Seeked`] one has to be removed.
+    pub async fn unsubscribe(self) {
+        drop(self.properties);
+        self.seeked_stream.into_inner().unsubscribe().await;
+    }
+}
+impl<'a> Stream for PlayerUpdateStream<'a> {
+    type Item = StreamYield<PlayerUpdate>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        if let Ready(Some(seeked)) = this.seeked_stream.as_mut().poll_next(cx) {
+            return Ready(Some(StreamYield::new(seeked.player_name, PlayerUpdate::Seeked(seeked.value))));
+        }
+
+        // Only finished once all of them are
+        match this.properties.as_mut().poll_next(cx) {
+            Ready(None) if !this.seeked_stream.is_terminated() => Pending,
+            poll => poll
+        }
+    }
+}
+
+/// An item of the [`PlaybackEventStream`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackEvent {
+    /// The [`PlaybackStatus`] changed (or, for the first item, the current one)
+    Status(Playback),
+    /// The player left the bus. This is always the last item.
+    PlayerGone
+}
+
+/// Yields the [`PlaybackStatus`] changes like [`ParsedPropertyStream`] does, but ends with a [`PlaybackEvent::PlayerGone`] once the player leaves the bus.
+/// <br>This way a closed player doesn't look the same as a stopped one. Created by [`Player::subscribe_playback`].
+#[pin_project]
+pub struct PlaybackEventStream<'a> {
+    #[pin]
+    status_stream: ParsedPropertyStream<'a, PlaybackStatus>,
+    #[pin]
+    owner_stream: OwnerChangedStream<'a>,
+    player_name: OwnedBusName,
+    gone: bool
+}
+impl<'a> PlaybackEventStream<'a> {
+    pub fn new(player_name: OwnedBusName, status_stream: ParsedPropertyStream<'a, PlaybackStatus>, owner_stream: OwnerChangedStream<'a>) -> Self {
+        Self {
+            status_stream,
+            owner_stream,
+            player_name,
+            gone: false
+        }
+    }
+}
+impl<'a> Stream for PlaybackEventStream<'a> {
+    type Item = StreamYield<PlaybackEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        use Poll::*;
+        let mut this = self.project();
+
+        if *this.gone {
+            return Ready(None);
+        }
+
+        loop {
+            match this.owner_stream.as_mut().poll_next(cx) {
+                // A new owner (the player restarted under the same name) is not interesting here
+                Ready(Some(Some(_))) => continue,
+                Ready(Some(None)) | Ready(None) => {
+                    *this.gone = true;
+                    return Ready(Some(StreamYield::new(this.player_name.clone(), PlaybackEvent::PlayerGone)));
+                },
+                Pending => break
+            }
+        }
+
+        match this.status_stream.as_mut().poll_next(cx) {
+            Pending => Pending,
+            Ready(Some(status)) => Ready(Some(StreamYield::new(status.player_name, PlaybackEvent::Status(status.value)))),
+            Ready(None) => {
+                *this.gone = true;
+                Ready(Some(StreamYield::new(this.player_name.clone(), PlaybackEvent::PlayerGone)))
+            }
+        }
+    }
+}
+
+
+/// A stream that can be cloned, and every clone receives every item of the single underlying stream. See [`Player::shared_metadata_stream`].
+/// <br>Each clone buffers up to `capacity` items. A clone that falls further behind skips the oldest items (as per the [`broadcast`] semantics),
+/// rather than slowing down the others.
+/// <br><br>The underlying stream is driven on a tokio task, which stops once the stream ends, or once all the clones are dropped and a new item arrives.
+pub struct SharedStream<T> {
+    inner: Pin<Box<dyn Stream<Item = T> + Send>>,
+    // Never read, only kept to create the receivers of new clones
+    origin: broadcast::Receiver<T>,
+}
+impl<T> SharedStream<T>
+where
+    T: Clone + Send + 'static
+{
+    pub fn new<S>(source: S, capacity: usize) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static
+    {
+        let (sender, origin) = broadcast::channel(capacity);
+        // Subscribe before spawning, to not miss the first items
+        let shared = Self::from_receiver(origin);
+
+        tokio::spawn(async move {
+            pin_mut!(source);
+            while let Some(item) = source.next().await {
+                // Every receiver is gone
+                if sender.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+
+        shared
+    }
+
+    fn from_receiver(origin: broadcast::Receiver<T>) -> Self {
+        let inner = stream::unfold(origin.resubscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(item) => return Some((item, receiver)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Self {
+            inner: Box::pin(inner),
+            origin
+        }
+    }
+}
+impl<T> Clone for SharedStream<T>
+where
+    T: Clone + Send + 'static
+{
+    fn clone(&self) -> Self {
+        Self::from_receiver(self.origin.resubscribe())
+    }
+}
+impl<T> Stream for SharedStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+
+/// A boxed future resolving to the freshly parsed value of a property.
+type PendingParse<'a, T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'a>>;
+
 #[pin_project]
 /// A [`PropertyStream`](https://docs.rs/zbus/latest/zbus/proxy/struct.PropertyStream.html), but the raw data is parsed into the corresponding [`Property`](super::properties::Property) type.
 /// <br>Note: The first time the stream is polled it will return the <b>current</b> state.
+/// <br>This doesn't add match rules of its own, as it's fed by the single `PropertiesChanged` subscription of the [`Player`](super::Player), so dropping it is all the cleanup needed.
+/// <br>Values of the wrong type (like a Metadata array instead of a dict) are skipped, rather than ending the stream.
 /// 
 /// <br>For signals check out [`ParsedSignalStream`]
 pub struct ParsedPropertyStream<'a, P>
@@ -187,7 +904,7 @@ where
     #[pin]
     raw_stream: PropertyStream<'a, P>,
     #[pin]
-    pending: Option<Pin<Box<dyn Future<Output = Result<P::ParseAs, zbus::Error>> + 'a >>>,
+    pending: Option<PendingParse<'a, P::ParseAs>>,
 
     p: P,
     player_name: OwnedBusName
@@ -231,6 +948,8 @@ where
 
                         return Ready(Some(StreamYield::new(this.player_name.clone(), parsed)))
                     },
+                    // A value of the wrong type, which some broken players send. It's skipped, as the next one may be fine.
+                    Ready(Err(Error::ParseFailed { .. })) => this.pending.set(None),
                     Ready(Err(_e)) => {
                         this.pending.set(None);
                         return Ready(None)
@@ -246,11 +965,12 @@ where
                 Ready(Some(value)) => {
 
                     // If something has changed, create a future that can be polled, to get what changed, and return Pending
-                    let fut: Pin<Box<dyn Future<Output = Result<P::ParseAs, zbus::Error>>>> = Box::pin(async move {
+                    let property = this.p.name();
+                    let fut: PendingParse<'a, P::ParseAs> = Box::pin(async move {
                         // It is safe to unwrap, as it could only fail on UNIX platforms, if Value::Fd is being parsed
-                        let value: OwnedValue = value.get_raw().await?.deref().clone().try_into_owned().unwrap();
-                        let converted: P::ParseAs = value.try_into().map_err(|_e| zbus::Error::Variant(zbus::zvariant::Error::IncorrectType))?;
-                        Ok(converted)
+                        let value: OwnedValue = super::value::unwrap_variant(value.get_raw().await?.deref().clone().try_into_owned().unwrap());
+                        let signature = value.value_signature().to_string();
+                        super::value::parse(value).ok_or(Error::ParseFailed { property, signature })
                     });
                     *this.pending = Some(fut);
                 }
@@ -263,6 +983,7 @@ where
 
 #[pin_project]
 /// A [`SignalStream`](https://docs.rs/zbus/latest/zbus/proxy/struct.SignalStream.html), but the raw data is parsed into the corresponding [`Signal`](super::signals::Signal) type.
+/// <br>Every instance adds a match rule to the bus, which is removed shortly after it's dropped. To remove it right away, use [`unsubscribe`](Self::unsubscribe).
 /// 
 /// <br>For properties check out [`ParsedPropertyStream`]
 pub struct ParsedSignalStream<'a, S>
@@ -288,6 +1009,11 @@ where
             player_name
         }
     }
+
+    /// Drops the stream, and waits until its match rule is removed from the bus.
+    pub async fn unsubscribe(self) {
+        self.raw_stream.async_drop().await;
+    }
 }
 impl<'a, S> Stream for ParsedSignalStream<'a, S> 
 where 
@@ -301,18 +1027,17 @@ where
         use Poll::*;
         let mut this = self.project();
 
-        // Try to poll the raw stream, if something is changed, start polling what changed.
-        match this.raw_stream.as_mut().poll_next(cx) {
-            Pending => Pending,
-            Ready(None) => Ready(None),  // The raw stream is finished, meaning this stream should finish too
-            Ready(Some(msg)) => {
-                let body = msg.body();
-                let parsed: S::ParseAs = match body.deserialize_unchecked() {   // Lets hope unchecked is fine
-                    Ok(v) => v,
-                    Err(_e) => return Ready(None)
-                };
+        loop {
+            match this.raw_stream.as_mut().poll_next(cx) {
+                Pending => return Pending,
+                Ready(None) => return Ready(None),  // The raw stream is finished, meaning this stream should finish too
+                Ready(Some(msg)) => {
+                    // A body with the wrong signature is skipped, like with the properties, so a single malformed signal doesn't end the stream
+                    let body = msg.body();
+                    let Ok(parsed) = body.deserialize::<S::ParseAs>() else { continue };
 
-                Ready(Some(StreamYield::new(this.player_name.clone(), this.s.into_output(parsed))))
+                    return Ready(Some(StreamYield::new(this.player_name.clone(), this.s.into_output(parsed))));
+                }
             }
         }
     }
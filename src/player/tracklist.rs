@@ -0,0 +1,53 @@
+//! Methods of the [`Interface::TrackList`], which only some players (like VLC, or Audacious) implement. See [`Player::interfaces`] to check.
+
+use std::collections::HashMap;
+
+use zbus::zvariant::{ObjectPath, OwnedValue};
+
+use crate::Error;
+
+use super::{Interface, Metadata, Player, properties};
+
+impl Player {
+    /// Gets the metadata of the tracks, like the ones in [`properties::Tracks`]. Tracks the player doesn't know are left out of the result.
+    pub async fn get_tracks_metadata(&self, track_ids: &[impl AsRef<str>]) -> Result<Vec<Metadata>, Error> {
+        let track_ids = track_ids
+            .iter()
+            .map(|track_id| track_path(track_id.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tracks: Vec<HashMap<String, OwnedValue>> = self.call_method("GetTracksMetadata", (track_ids,), Interface::TrackList).await?;
+
+        Ok(tracks.into_iter().map(Metadata::from).collect())
+    }
+
+    /// Adds the URI to the tracklist after the track `after`, or at the start if it's [`NO_TRACK`](super::NO_TRACK). If `set_as_current` is true, it's played right away.
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanEditTracks`] is false.
+    pub async fn add_track(&self, uri: impl AsRef<str>, after: impl AsRef<str>, set_as_current: bool) -> Result<(), Error> {
+        self.require(properties::CanEditTracks).await?;
+
+        let after = track_path(after.as_ref())?;
+        self.call_method("AddTrack", (uri.as_ref(), after, set_as_current), Interface::TrackList).await
+    }
+
+    /// Removes the track from the tracklist. If it's the current one, the player may keep playing it, or skip to another.
+    /// <br>Returns [`Error::NotControllable`] without calling the player if [`properties::CanEditTracks`] is false.
+    pub async fn remove_track(&self, track_id: impl AsRef<str>) -> Result<(), Error> {
+        self.require(properties::CanEditTracks).await?;
+
+        let track_id = track_path(track_id.as_ref())?;
+        self.call_method("RemoveTrack", (track_id,), Interface::TrackList).await
+    }
+
+    /// Skips to the track in the tracklist. If the playback is paused or stopped, it remains that way.
+    pub async fn go_to(&self, track_id: impl AsRef<str>) -> Result<(), Error> {
+        let track_id = track_path(track_id.as_ref())?;
+        self.call_method("GoTo", (track_id,), Interface::TrackList).await
+    }
+}
+
+/// Checks that the trackid is an object path, as the player couldn't even be called with it otherwise
+pub(super) fn track_path(track_id: &str) -> Result<ObjectPath<'_>, Error> {
+    ObjectPath::try_from(track_id)
+        .map_err(|_| Error::InvalidTrackId(track_id.to_string()))
+}
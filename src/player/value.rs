@@ -0,0 +1,86 @@
+//! Reading the values players send, including the oddly encoded ones.
+//! <br>Every reader goes through these, so a player's quirk only has to be handled once.
+
+use zbus::zvariant::{OwnedValue, Str, Value};
+
+/// Unwraps a variant nested into a variant (into a variant...), as some players (depending on their D-Bus library) wrap the values once more than needed.
+/// <br>Values that aren't nested are returned as is.
+pub(crate) fn unwrap_variant(value: OwnedValue) -> OwnedValue {
+    let mut value = value;
+
+    while let Value::Value(inner) = &*value {
+        match OwnedValue::try_from(inner.as_ref()) {
+            Ok(inner) => value = inner,
+            // Only happens if it contains a file descriptor, which none of the MPRIS properties do
+            Err(_) => break
+        }
+    }
+
+    value
+}
+
+/// The innermost value of nested variants, like [`unwrap_variant`], but without taking ownership
+fn innermost<'a>(value: &'a Value<'a>) -> &'a Value<'a> {
+    match value {
+        Value::Value(inner) => innermost(inner),
+        value => value
+    }
+}
+
+/// Reads a string. Unlike `to_string`, this doesn't wrap it in quotes.
+/// <br>Object paths are read as strings too, as some players send one where the other should be.
+pub(crate) fn as_string(value: &OwnedValue) -> Option<String> {
+    let value = innermost(value);
+
+    value.downcast_ref::<String>().ok()
+        .or_else(|| value.downcast_ref::<zbus::zvariant::ObjectPath>().ok().map(|path| path.to_string()))
+}
+
+/// Reads a list of strings, also accepting a single string (as some players send that instead of an array).
+pub(crate) fn as_string_vec(value: &OwnedValue) -> Option<Vec<String>> {
+    let value = innermost(value);
+
+    value.try_clone().ok()
+        .and_then(|value| Vec::<String>::try_from(value).ok())
+        .or_else(|| value.downcast_ref::<String>().ok().map(|v| vec![v]))
+}
+
+/// Reads an integer regardless of which width the player decided to send it as.
+/// <br>The specs say `i` (int32) for most of them, but players send anything from `y` (byte) to `t` (uint64). A `t` that doesn't fit is `None`.
+pub(crate) fn as_i64(value: &OwnedValue) -> Option<i64> {
+    let value = innermost(value);
+
+    value.downcast_ref::<i16>().ok().map(i64::from)
+        .or_else(|| value.downcast_ref::<i32>().ok().map(i64::from))
+        .or_else(|| value.downcast_ref::<u32>().ok().map(i64::from))
+        .or_else(|| value.downcast_ref::<i64>().ok())
+        .or_else(|| value.downcast_ref::<u64>().ok().and_then(|v| i64::try_from(v).ok()))
+        .or_else(|| value.downcast_ref::<u16>().ok().map(i64::from))
+        .or_else(|| value.downcast_ref::<u8>().ok().map(i64::from))
+}
+
+/// Reads a floating point number, also accepting integers (as some players send the ratings like that)
+pub(crate) fn as_f64(value: &OwnedValue) -> Option<f64> {
+    innermost(value).downcast_ref::<f64>().ok()
+        .or_else(|| as_i64(value).map(|v| v as f64))
+}
+
+/// Converts the value into the type of a property, coercing it if the player sent a similar type than what the specs say.
+/// <br>Nested variants are unwrapped, then the value is tried as it is, then as an [integer](as_i64), a [float](as_f64), a [string](as_string) and a [list of strings](as_string_vec).
+/// So for example a `Position` sent as a `t` (uint64), or a `Rate` sent as an `i` (int32) still parse.
+pub(crate) fn parse<T: TryFrom<OwnedValue>>(value: OwnedValue) -> Option<T> {
+    let value = unwrap_variant(value);
+
+    if let Some(parsed) = value.try_clone().ok().and_then(|value| T::try_from(value).ok()) {
+        return Some(parsed);
+    }
+
+    let coerced = [
+        as_i64(&value).map(OwnedValue::from),
+        as_f64(&value).map(OwnedValue::from),
+        as_string(&value).map(|v| OwnedValue::from(Str::from(v))),
+        as_string_vec(&value).and_then(|v| OwnedValue::try_from(Value::from(v)).ok()),
+    ];
+
+    coerced.into_iter().flatten().find_map(|value| T::try_from(value).ok())
+}